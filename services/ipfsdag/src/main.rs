@@ -6,7 +6,7 @@ use marine_rs_sdk::marine;
 use marine_rs_sdk::module_manifest;
 use marine_rs_sdk::MountedBinaryResult;
 use marine_rs_sdk::WasmLoggerBuilder;
-use types::{IpfsDagGetResult, IpfsDagPutResult};
+use types::{IpfsDagGetResult, IpfsDagPutResult, IpfsUnpinResult};
 
 use block::{deserialize, serialize};
 use eyre::Result;
@@ -156,6 +156,33 @@ pub fn get(hash: String, api_multiaddr: String, timeout_sec: u64) -> IpfsDagGetR
         .into()
 }
 
+#[marine]
+pub fn unpin(hash: String, api_multiaddr: String, timeout_sec: u64) -> IpfsUnpinResult {
+    let address = if api_multiaddr.is_empty() {
+        DEFAULT_IPFS_MULTIADDR.to_string()
+    } else {
+        api_multiaddr
+    };
+
+    let t = if timeout_sec == 0 {
+        DEFAULT_TIMEOUT_SEC
+    } else {
+        timeout_sec
+    };
+
+    log::info!("unpin called with hash {}", hash);
+
+    let args = vec![String::from("pin"), String::from("rm"), hash];
+
+    let cmd = make_cmd_args(args, address, t);
+
+    log::info!("ipfs pin rm args {:?}", cmd);
+
+    unwrap_mounted_binary_result(ipfs(cmd))
+        .map(|res| res.trim().to_string())
+        .into()
+}
+
 #[marine]
 #[link(wasm_import_module = "host")]
 extern "C" {
@@ -0,0 +1,152 @@
+use marine_rs_sdk::marine;
+use serde::Deserialize;
+
+use crate::error::ServiceError;
+use crate::metadatas::Metadata;
+use crate::normalize::{normalize_address, normalize_chain_id, normalize_token_id};
+
+/**
+ * CAIP-19 identity of a data_key. Collection keys have no token id and map to the asset type,
+ * `eip155:1/erc721:0x…`. Without a known namespace the asset id stays empty
+ */
+#[marine]
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Asset {
+    pub data_key: String,
+    pub asset_id: String,
+    pub chain_id: String,
+    pub namespace: String,
+    pub token_address: String,
+    pub token_id: String,
+}
+
+impl Asset {
+    pub fn new(chain_id: &str, namespace: &str, token_address: &str, token_id: &str) -> Self {
+        let chain_id = normalize_chain_id(chain_id);
+        let token_address = normalize_address(token_address);
+        let token_id = normalize_token_id(token_id);
+        let namespace = namespace.trim().to_lowercase();
+
+        let mut asset_id = "".to_string();
+        if !namespace.is_empty() {
+            asset_id = format!("{}/{}:{}", chain_id, namespace, token_address);
+            if !token_id.is_empty() {
+                asset_id = format!("{}/{}", asset_id, token_id);
+            }
+        }
+
+        Self {
+            data_key: Metadata::generate_data_key(chain_id.clone(), token_address.clone(), token_id.clone()),
+            asset_id,
+            chain_id,
+            namespace,
+            token_address,
+            token_id,
+        }
+    }
+
+    /**
+     * Reads `<chain>/<namespace>:<address>` with an optional `/<token id>`
+     */
+    pub fn parse(asset_id: &str) -> Result<Self, ServiceError> {
+        let invalid = || ServiceError::InvalidAssetId(asset_id.to_string());
+        let segments: Vec<&str> = asset_id.trim().split('/').collect();
+
+        if segments.len() < 2 || segments.len() > 3 {
+            return Err(invalid());
+        }
+
+        let (chain_namespace, chain_reference) = segments[0].split_once(':').ok_or_else(invalid)?;
+        let (namespace, token_address) = segments[1].split_once(':').ok_or_else(invalid)?;
+        let token_id = segments.get(2).copied().unwrap_or_default();
+
+        if chain_namespace.is_empty()
+            || chain_reference.is_empty()
+            || namespace.is_empty()
+            || token_address.is_empty()
+            || (segments.len() == 3 && token_id.is_empty())
+        {
+            return Err(invalid());
+        }
+
+        Ok(Self::new(segments[0], namespace, token_address, token_id))
+    }
+
+    /**
+     * Data keys are bs58 and never contain ':', so anything else is read as an asset id
+     */
+    pub fn resolve_data_key(data_key_or_asset_id: &str) -> Result<String, ServiceError> {
+        if data_key_or_asset_id.contains(':') {
+            Ok(Self::parse(data_key_or_asset_id)?.data_key)
+        } else {
+            Ok(data_key_or_asset_id.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_token() {
+        let asset = Asset::parse("eip155:1/erc1155:0xABCDEF/0x10").unwrap();
+
+        assert_eq!(asset.chain_id, "eip155:1");
+        assert_eq!(asset.namespace, "erc1155");
+        assert_eq!(asset.token_address, "0xabcdef");
+        assert_eq!(asset.token_id, "16");
+        assert_eq!(asset.asset_id, "eip155:1/erc1155:0xabcdef/16");
+        assert_eq!(
+            asset.data_key,
+            Metadata::generate_data_key("1".to_string(), "0xabcdef".to_string(), "16".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_a_collection() {
+        let asset = Asset::parse("eip155:0x89/ERC721:0xabc").unwrap();
+
+        assert_eq!(asset.chain_id, "eip155:137");
+        assert_eq!(asset.namespace, "erc721");
+        assert_eq!(asset.token_id, "");
+        assert_eq!(asset.asset_id, "eip155:137/erc721:0xabc");
+        assert_eq!(
+            asset.data_key,
+            Metadata::generate_token_key("137".to_string(), "0xabc".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_ids() {
+        for asset_id in [
+            "",
+            "eip155:1",
+            "eip155/erc721:0xabc",
+            "eip155:1/erc721",
+            "eip155:1/:0xabc",
+            "eip155:1/erc721:0xabc/",
+            "eip155:1/erc721:0xabc/1/2",
+        ] {
+            assert!(Asset::parse(asset_id).is_err(), "{}", asset_id);
+        }
+    }
+
+    #[test]
+    fn leaves_an_unknown_namespace_without_asset_id() {
+        let asset = Asset::new("1", "", "0xABC", "1");
+
+        assert_eq!(asset.namespace, "");
+        assert_eq!(asset.asset_id, "");
+        assert_eq!(asset.data_key, Asset::new("1", "erc1155", "0xabc", "1").data_key);
+    }
+
+    #[test]
+    fn resolves_data_keys_and_asset_ids() {
+        let asset = Asset::parse("eip155:1/erc721:0xabc/1").unwrap();
+
+        assert_eq!(Asset::resolve_data_key("eip155:1/erc721:0xabc/1").unwrap(), asset.data_key);
+        assert_eq!(Asset::resolve_data_key(&asset.data_key).unwrap(), asset.data_key);
+        assert!(Asset::resolve_data_key("eip155:1").is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::asset::Asset;
+use crate::defaults::{ASSETS_TABLE_NAME, METADATAS_TABLE_NAME};
+use crate::error::ServiceError;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+
+impl Storage {
+    pub fn create_assets_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                data_key TEXT PRIMARY KEY UNIQUE,
+                asset_id TEXT not null,
+                chain_id TEXT not null,
+                namespace TEXT not null,
+                token_address TEXT not null,
+                token_id TEXT not null
+            )",
+            ASSETS_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_assets_table error: {}", error);
+        }
+    }
+
+    /**
+     * Records the assets of data_keys published or indexed before assets were kept.
+     * Their namespace is unknown, an asset already recorded is left as it is
+     */
+    pub fn backfill_assets(&self) -> Result<(), ServiceError> {
+        for coordinates in self.get_key_coordinates()? {
+            let asset = Asset::new(&coordinates.chain_id, "", &coordinates.token_address, &coordinates.token_id);
+
+            if let Err(e) = self.write_asset(asset) {
+                log::info!("{}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Records the asset a data_key was derived from. An asset with a known namespace, given through
+     * an asset id or a cron, replaces the recorded one and the records of the data_key follow it
+     */
+    pub fn write_asset(&self, asset: Asset) -> Result<(), ServiceError> {
+        let known = !asset.namespace.is_empty();
+        let conflict = if known { "replace" } else { "ignore" };
+
+        let statement = format!(
+            "insert or {} into {} (data_key, asset_id, chain_id, namespace, token_address, token_id) values ('{}', '{}', '{}', '{}', '{}', '{}')",
+            conflict,
+            ASSETS_TABLE_NAME,
+            asset.data_key,
+            Storage::escape(&asset.asset_id),
+            Storage::escape(&asset.chain_id),
+            Storage::escape(&asset.namespace),
+            Storage::escape(&asset.token_address),
+            Storage::escape(&asset.token_id),
+        );
+
+        Storage::execute(statement)?;
+
+        if known {
+            Storage::execute(format!(
+                "update {} set asset_id = '{}' where data_key = '{}'",
+                METADATAS_TABLE_NAME,
+                Storage::escape(&asset.asset_id),
+                asset.data_key,
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_asset(&self, data_key: String) -> Result<Asset, ServiceError> {
+        let statement = format!("SELECT * FROM {} WHERE data_key = '{}'", ASSETS_TABLE_NAME, data_key);
+
+        let result = Storage::read(statement)?;
+        match read(result) {
+            Ok(assets) => assets
+                .first()
+                .cloned()
+                .ok_or(ServiceError::RecordNotFound(data_key)),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<Asset>, ServiceError> {
+    let mut assets = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::Asset(asset) => assets.push(asset),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        ASSETS_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(assets)
+}
@@ -1,10 +1,39 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Deserialize)]
+use crate::error::ServiceError;
+use crate::get;
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Block {
     pub timestamp: u64,
     pub content: Value,
     pub previous: Value,
     pub transaction: Value,
 }
+
+impl Block {
+    /**
+     * Reads a block from IPFS by its CID
+     */
+    pub fn fetch(cid: String) -> Result<Self, ServiceError> {
+        let result = get(cid, "".to_string(), 0);
+
+        if !result.success {
+            return Err(ServiceError::InternalError(result.error));
+        }
+
+        Ok(serde_json::from_str(&result.block)?)
+    }
+
+    /**
+     * CID of the block this one was written on top of, if any
+     */
+    pub fn previous_cid(&self) -> Option<String> {
+        self.previous
+            .get("/")
+            .and_then(|v| v.as_str())
+            .filter(|cid| !cid.is_empty())
+            .map(|cid| cid.to_string())
+    }
+}
@@ -0,0 +1,16 @@
+use marine_rs_sdk::marine;
+use serde::Deserialize;
+
+/**
+ * Link from an origin metadata to the metadata a "clone" transaction copied it into.
+ * `depth` is the distance to the record a lineage query started from
+ */
+#[marine]
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct CloneEdge {
+    pub origin_hash: String,
+    pub clone_hash: String,
+    pub transaction_hash: String,
+    pub timestamp: u64,
+    pub depth: i64,
+}
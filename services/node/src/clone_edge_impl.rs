@@ -0,0 +1,177 @@
+use crate::clone_edge::CloneEdge;
+use crate::data_types::DataTypeClone;
+use crate::defaults::{
+    CLONE_EDGES_TABLE_NAME, CLONE_LINEAGE_MAX_DEPTH, METHOD_CLONE, RECEIPT_STATUS_SUCCESS, STATUS_DONE, TRANSACTIONS_TABLE_NAME,
+    TRANSACTION_RECEIPT_TABLE_NAME,
+};
+use crate::error::ServiceError;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+use crate::transactions_impl;
+
+impl Storage {
+    pub fn create_clone_edges_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                origin_hash TEXT not null,
+                clone_hash TEXT not null,
+                transaction_hash TEXT not null,
+                timestamp INTEGER not null,
+                PRIMARY KEY (origin_hash, clone_hash)
+            )",
+            CLONE_EDGES_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_clone_edges_table error: {}", error);
+        }
+    }
+
+    pub fn write_clone_edge(
+        &self,
+        origin_hash: String,
+        clone_hash: String,
+        transaction_hash: String,
+        timestamp: u64,
+    ) -> Result<(), ServiceError> {
+        let s = format!(
+            "insert or ignore into {} (origin_hash, clone_hash, transaction_hash, timestamp) values ('{}', '{}', '{}', '{}')",
+            CLONE_EDGES_TABLE_NAME, origin_hash, clone_hash, transaction_hash, timestamp,
+        );
+
+        Storage::execute(s)?;
+
+        Ok(())
+    }
+
+    /**
+     * Links the clones made before edges were recorded. The copy was written under the signer's key
+     * and alias since forks were validated, and under the origin's own before that
+     */
+    pub fn backfill_clone_edges(&self) -> Result<(), ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE method = '{}' AND status = {} AND hash IN (SELECT hash FROM {} WHERE status = {})
+            AND hash NOT IN (SELECT transaction_hash FROM {})",
+            TRANSACTIONS_TABLE_NAME,
+            METHOD_CLONE,
+            STATUS_DONE,
+            TRANSACTION_RECEIPT_TABLE_NAME,
+            RECEIPT_STATUS_SUCCESS,
+            CLONE_EDGES_TABLE_NAME
+        );
+
+        let result = Storage::read(statement)?;
+
+        for transaction in transactions_impl::read(result)? {
+            let data_clone = match serde_json::from_str::<DataTypeClone>(&transaction.data) {
+                Ok(data_clone) => data_clone,
+                Err(e) => {
+                    log::info!("{}", e);
+                    continue;
+                }
+            };
+
+            let origin = match self.get_owner_metadata(
+                data_clone.origin_data_key.clone(),
+                data_clone.origin_meta_contract_id.clone(),
+                data_clone.origin_public_key.clone(),
+                data_clone.origin_alias.clone(),
+                data_clone.origin_version.clone(),
+            ) {
+                Ok(origin) => origin,
+                Err(e) => {
+                    log::info!("{}", e);
+                    continue;
+                }
+            };
+
+            let clone = self
+                .get_owner_metadata(
+                    transaction.data_key.clone(),
+                    origin.meta_contract_id.clone(),
+                    transaction.public_key.clone(),
+                    transaction.alias.clone(),
+                    origin.version.clone(),
+                )
+                .or_else(|_| {
+                    self.get_owner_metadata(
+                        transaction.data_key.clone(),
+                        origin.meta_contract_id.clone(),
+                        origin.public_key.clone(),
+                        origin.alias.clone(),
+                        origin.version.clone(),
+                    )
+                });
+
+            match clone {
+                Ok(clone) if clone.hash != origin.hash => {
+                    self.write_clone_edge(origin.hash, clone.hash, transaction.hash, transaction.timestamp)?;
+                }
+                Ok(_) => {}
+                Err(e) => log::info!("{}", e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Origins a metadata was cloned from, nearest first
+     */
+    pub fn get_clone_ancestors(&self, metadata_hash: String) -> Result<Vec<CloneEdge>, ServiceError> {
+        let statement = format!(
+            "WITH RECURSIVE lineage(origin_hash, clone_hash, transaction_hash, timestamp, depth) AS (
+                SELECT origin_hash, clone_hash, transaction_hash, timestamp, 1 FROM {0} WHERE clone_hash = '{1}'
+                UNION ALL
+                SELECT e.origin_hash, e.clone_hash, e.transaction_hash, e.timestamp, l.depth + 1
+                FROM {0} e JOIN lineage l ON e.clone_hash = l.origin_hash
+                WHERE l.depth < {2}
+            ) SELECT * FROM lineage ORDER BY depth ASC",
+            CLONE_EDGES_TABLE_NAME, metadata_hash, CLONE_LINEAGE_MAX_DEPTH
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+
+    /**
+     * Clones of a metadata and their own clones, `depth` levels down
+     */
+    pub fn get_clone_descendants(&self, metadata_hash: String, depth: u32) -> Result<Vec<CloneEdge>, ServiceError> {
+        let statement = format!(
+            "WITH RECURSIVE lineage(origin_hash, clone_hash, transaction_hash, timestamp, depth) AS (
+                SELECT origin_hash, clone_hash, transaction_hash, timestamp, 1 FROM {0} WHERE origin_hash = '{1}'
+                UNION ALL
+                SELECT e.origin_hash, e.clone_hash, e.transaction_hash, e.timestamp, l.depth + 1
+                FROM {0} e JOIN lineage l ON e.origin_hash = l.clone_hash
+                WHERE l.depth < {2}
+            ) SELECT * FROM lineage ORDER BY depth ASC, timestamp ASC",
+            CLONE_EDGES_TABLE_NAME, metadata_hash, depth
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<CloneEdge>, ServiceError> {
+    let mut edges = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::CloneEdge(edge) => edges.push(edge),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        CLONE_EDGES_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(edges)
+}
@@ -1,9 +1,13 @@
+use std::cmp::{max, min};
+
 use marine_rs_sdk::marine;
 use marine_sqlite_connector::{State, Statement, Value};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
-use crate::defaults::{CRON_STATUS_DISABLE, CRON_STATUS_ENABLE};
+use crate::asset::Asset;
+use crate::metadatas::Metadata;
+use crate::defaults::{CRON_MAX_BLOCK_RANGE, CRON_STATUS_DISABLE, CRON_STATUS_ENABLE};
 use crate::storage_impl::{RQLiteResult, Row};
 use crate::{defaults::CRON_TABLE_NAME, storage_impl::Storage};
 use crate::{error::ServiceError, error::ServiceError::InternalError};
@@ -21,11 +25,17 @@ pub struct Cron {
     pub meta_contract_id: String,
     pub node_url: String,
     pub public_key: String,
+    pub asset_id: String,
+    pub last_processed_block: u64,
+    pub start_block: u64,
+    pub end_block: u64,
+    pub confirmations: u64,
 }
 
 impl Cron {
     pub fn new(token_key: String, address: String, topic: String, token_type: String, chain: String, status: i64, meta_contract_id: String, node_url: String, public_key: String) -> Self {
       let hash = Self::generate_hash(address.clone(), topic.clone(), chain.clone());
+      let asset_id = Asset::new(&chain, &token_type, &address, "").asset_id;
 
       Self {
           hash,
@@ -38,9 +48,51 @@ impl Cron {
           meta_contract_id,
           node_url,
           public_key,
+          asset_id,
+          last_processed_block: 0,
+          start_block: 0,
+          end_block: 0,
+          confirmations: 0,
       }
     }
 
+    /**
+     * Key of the token an event is about. Keys carried by events are computed off-chain, possibly
+     * without normalization, so the key is derived again when the event has a token id
+     */
+    pub fn event_data_key(&self, token_id: &str, data_key: &str) -> String {
+        if token_id.trim().is_empty() {
+            data_key.to_string()
+        } else {
+            Metadata::generate_data_key(self.chain.clone(), self.address.clone(), token_id.to_string())
+        }
+    }
+
+    /**
+     * Next block range the poller has to scan, up to the last confirmed block and the cron's end block.
+     * None when the cursor already caught up
+     */
+    pub fn next_range(&self, latest_block: u64) -> Option<(u64, u64)> {
+        let latest_block = latest_block.saturating_sub(self.confirmations);
+
+        let from = if self.last_processed_block == 0 {
+            self.start_block
+        } else {
+            max(self.last_processed_block + 1, self.start_block)
+        };
+
+        let mut to = min(latest_block, from + CRON_MAX_BLOCK_RANGE - 1);
+        if self.end_block > 0 {
+            to = min(to, self.end_block);
+        }
+
+        if from > to {
+            None
+        } else {
+            Some((from, to))
+        }
+    }
+
     pub fn generate_hash(
         address: String,
         topic: String,
@@ -86,6 +138,15 @@ pub struct SerdeCron {
     pub status: i64,
     // pub meta_contract_id: String,
     pub node_url: String,
+    /** First block to scan, 0 starts at the chain head the cron is first polled at */
+    #[serde(default)]
+    pub start_block: u64,
+    /** Last block to scan, 0 follows the chain head */
+    #[serde(default)]
+    pub end_block: u64,
+    /** Blocks an event has to be buried under before it is indexed */
+    #[serde(default)]
+    pub confirmations: u64,
 }
 
 impl Storage {
@@ -103,7 +164,12 @@ impl Storage {
             meta_contract_id varchar(255) null,
             node_url text null,
             last_processed_block integer not null default(0),
-            public_key TEXT not null
+            public_key TEXT not null,
+            asset_id TEXT not null default '',
+            start_block integer not null default(0),
+            end_block integer not null default(0),
+            confirmations integer not null default(0),
+            suspended_by TEXT not null default ''
         )",
             CRON_TABLE_NAME
         );
@@ -113,6 +179,12 @@ impl Storage {
         if let Err(error) = result {
             println!("create_meta_contract_table error: {}", error);
         }
+
+        Storage::add_column(CRON_TABLE_NAME, "asset_id", "TEXT not null default ''");
+        Storage::add_column(CRON_TABLE_NAME, "start_block", "integer not null default(0)");
+        Storage::add_column(CRON_TABLE_NAME, "end_block", "integer not null default(0)");
+        Storage::add_column(CRON_TABLE_NAME, "confirmations", "integer not null default(0)");
+        Storage::add_column(CRON_TABLE_NAME, "suspended_by", "TEXT not null default ''");
     }
 
     /**
@@ -120,7 +192,7 @@ impl Storage {
      */
     pub fn write_cron(&self, cron: Cron) -> Result<(), ServiceError> {
         let s = format!(
-            "insert into {} (hash, token_key, address, token_type, chain, topic, status, last_processed_block, meta_contract_id, node_url, public_key) values ('{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}')",
+            "insert into {} (hash, token_key, address, token_type, chain, topic, status, last_processed_block, meta_contract_id, node_url, public_key, asset_id, start_block, end_block, confirmations) values ('{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}')",
             CRON_TABLE_NAME,
             cron.hash,
             cron.token_key,
@@ -129,10 +201,14 @@ impl Storage {
             cron.chain,
             cron.topic,
             cron.status,
-            0,
+            cron.last_processed_block,
             cron.meta_contract_id,
             cron.node_url,
             cron.public_key,
+            cron.asset_id,
+            cron.start_block,
+            cron.end_block,
+            cron.confirmations,
         );
 
         let result = Storage::execute(s);
@@ -145,32 +221,38 @@ impl Storage {
             }
         }
     }
-    pub fn cron_disable(&self, meta_contract_id: String) -> Result<(), ServiceError> {
+    /**
+     * Stops the running crons of a meta contract and marks them with the suspension that stopped them
+     */
+    pub fn cron_disable(&self, meta_contract_id: String, suspension_hash: String) -> Result<(), ServiceError> {
         let s = format!(
             "
           update {}
-          set status = '{}'
-          where meta_contract_id = '{}';
+          set status = '{}', suspended_by = '{}'
+          where meta_contract_id = '{}' AND status = '{}';
           ",
-            CRON_TABLE_NAME, CRON_STATUS_DISABLE, meta_contract_id
+            CRON_TABLE_NAME, CRON_STATUS_DISABLE, suspension_hash, meta_contract_id, CRON_STATUS_ENABLE
         );
 
-        Storage::execute(s);
+        Storage::execute(s)?;
 
         Ok(())
     }
 
+    /**
+     * Restarts only the crons a suspension stopped, the ones disabled or finished before stay off
+     */
     pub fn cron_enable(&self, meta_contract_id: String) -> Result<(), ServiceError> {
         let s = format!(
             "
           update {}
-          set status = '{}'
-          where meta_contract_id = '{}';
+          set status = '{}', suspended_by = ''
+          where meta_contract_id = '{}' AND suspended_by != '';
           ",
             CRON_TABLE_NAME, CRON_STATUS_ENABLE, meta_contract_id
         );
 
-        Storage::execute(s);
+        Storage::execute(s)?;
 
         Ok(())
     }
@@ -181,10 +263,13 @@ impl Storage {
         "
           update {}
           set meta_contract_id = '{}',
-          node_url = '{}'
+          node_url = '{}',
+          start_block = {},
+          end_block = {},
+          confirmations = {}
           where hash = '{}';
           ",
-            CRON_TABLE_NAME, cron.meta_contract_id, cron.node_url, hash
+            CRON_TABLE_NAME, cron.meta_contract_id, cron.node_url, cron.start_block, cron.end_block, cron.confirmations, hash
         );
 
         let result = Storage::execute(statement);
@@ -220,6 +305,44 @@ impl Storage {
           }
     }
 
+    /**
+     * Pins the start block of a cron created with 0, which means the chain head at its first poll
+     */
+    pub fn set_cron_start_block(&self, hash: String, start_block: u64) -> Result<(), ServiceError> {
+        let statement = format!(
+            "update {} set start_block = {} where hash = '{}'",
+            CRON_TABLE_NAME, start_block, hash
+        );
+
+        Storage::execute(statement)?;
+        Ok(())
+    }
+
+    /**
+     * Moves the cursor of a cron forward once the poller processed every block up to `block`.
+     * A cron that reached its end block is disabled
+     */
+    pub fn advance_cron_cursor(&self, hash: String, block: u64) -> Result<Cron, ServiceError> {
+        let mut cron = self.get_cron_by_hash(hash.clone())?;
+
+        if block > cron.last_processed_block {
+            let statement = format!(
+                "update {} set last_processed_block = '{}' where hash = '{}' and last_processed_block < {}",
+                CRON_TABLE_NAME, block, hash, block
+            );
+
+            Storage::execute(statement)?;
+            cron.last_processed_block = block;
+        }
+
+        if cron.end_block > 0 && cron.last_processed_block >= cron.end_block && cron.status == CRON_STATUS_ENABLE {
+            self.update_cron_status(hash, CRON_STATUS_DISABLE)?;
+            cron.status = CRON_STATUS_DISABLE;
+        }
+
+        Ok(cron)
+    }
+
     pub fn get_cron_by_hash(&self, hash: String) -> Result<Cron, ServiceError> {
         let statement = format!("SELECT * FROM {} WHERE hash = '{}'",CRON_TABLE_NAME, hash);
 
@@ -307,3 +430,66 @@ pub fn read(result: RQLiteResult) -> Result<Vec<Cron>, ServiceError> {
   Ok(txs)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cron(last_processed_block: u64, start_block: u64, end_block: u64, confirmations: u64) -> Cron {
+        Cron {
+            last_processed_block,
+            start_block,
+            end_block,
+            confirmations,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn starts_at_the_start_block() {
+        assert_eq!(cron(0, 100, 0, 0).next_range(150), Some((100, 150)));
+        assert_eq!(cron(0, 100, 0, 0).next_range(99), None);
+    }
+
+    #[test]
+    fn resumes_after_the_cursor() {
+        assert_eq!(cron(120, 100, 0, 0).next_range(150), Some((121, 150)));
+        assert_eq!(cron(150, 100, 0, 0).next_range(150), None);
+        assert_eq!(cron(50, 100, 0, 0).next_range(150), Some((100, 150)));
+    }
+
+    #[test]
+    fn caps_the_range_size() {
+        assert_eq!(
+            cron(0, 1, 0, 0).next_range(1_000_000),
+            Some((1, CRON_MAX_BLOCK_RANGE))
+        );
+    }
+
+    #[test]
+    fn stops_at_the_end_block() {
+        assert_eq!(cron(0, 100, 120, 0).next_range(150), Some((100, 120)));
+        assert_eq!(cron(120, 100, 120, 0).next_range(150), None);
+    }
+
+    #[test]
+    fn derives_event_keys_from_the_token_id() {
+        let cron = Cron {
+            chain: "1".to_string(),
+            address: "0xABC".to_string(),
+            ..Default::default()
+        };
+        let data_key = Metadata::generate_data_key("eip155:1".to_string(), "0xabc".to_string(), "16".to_string());
+
+        assert_eq!(cron.event_data_key("0x10", "legacy"), data_key);
+        assert_eq!(cron.event_data_key("16", data_key.as_str()), data_key);
+        assert_eq!(cron.event_data_key("", "legacy"), "legacy");
+    }
+
+    #[test]
+    fn waits_for_confirmations() {
+        assert_eq!(cron(0, 100, 0, 12).next_range(150), Some((100, 138)));
+        assert_eq!(cron(138, 100, 0, 12).next_range(150), None);
+        assert_eq!(cron(0, 0, 0, 12).next_range(5), Some((0, 0)));
+    }
+}
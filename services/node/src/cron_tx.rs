@@ -1,4 +1,6 @@
+use crate::asset::Asset;
 use crate::defaults::STATUS_PENDING;
+use crate::defaults::{CRON_TX_STATUS_REVERTED, CRON_TX_STATUS_SUCCESS};
 use crate::storage_impl::{RQLiteResult, Row};
 use crate::{defaults::CRON_TX_TABLE_NAME, storage_impl::Storage};
 use marine_rs_sdk::marine;
@@ -25,6 +27,8 @@ pub struct CronTx {
     pub token_id: String,
     pub data_key: String,
     pub token_key: String,
+    pub asset_id: String,
+    pub block_hash: String,
 }
 
 impl CronTx {
@@ -53,6 +57,8 @@ impl CronTx {
         tx_hash.clone(),
         token_id.clone(),
       );
+      let asset_id = Asset::new(&chain, &token_type, &address, &token_id).asset_id;
+
       Self {
         hash,
         address,
@@ -69,6 +75,8 @@ impl CronTx {
         token_id,
         data_key,
         token_key,
+        asset_id,
+        block_hash: "".to_string(),
       }
     }
 
@@ -119,6 +127,8 @@ impl Storage {
           token_id TEXT NULL,
           data_key TEXT NULL,
           token_key TEXT NULL,
+          asset_id TEXT not null default '',
+          block_hash TEXT not null default '',
           UNIQUE(address, chain, topic, tx_hash)
       )",
           CRON_TX_TABLE_NAME
@@ -129,6 +139,9 @@ impl Storage {
       if let Err(error) = result {
           println!("create_cron_tx_table error: {}", error);
       }
+
+      Storage::add_column(CRON_TX_TABLE_NAME, "asset_id", "TEXT not null default ''");
+      Storage::add_column(CRON_TX_TABLE_NAME, "block_hash", "TEXT not null default ''");
   }
 
   /**
@@ -136,7 +149,7 @@ impl Storage {
    */
   pub fn write_cron_tx(&self, cron: CronTx) -> Result<(), ServiceError> {
     let s = format!(
-        "insert into {} (
+        "insert or replace into {} (
           hash,
           address, 
           token_type, 
@@ -151,8 +164,10 @@ impl Storage {
           error_text,
           token_id,
           data_key,
-          token_key
-        ) values ('{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}')",
+          token_key,
+          asset_id,
+          block_hash
+        ) values ('{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}')",
         CRON_TX_TABLE_NAME,
         cron.hash,
         cron.address,
@@ -169,6 +184,8 @@ impl Storage {
         cron.token_id,
         cron.data_key,
         cron.token_key,
+        cron.asset_id,
+        cron.block_hash,
     );
 
     let result = Storage::execute(s);
@@ -233,6 +250,58 @@ impl Storage {
     }
   }
 
+  pub fn get_cron_tx(&self, hash: String) -> Result<CronTx, ServiceError> {
+    let statement = format!("SELECT * FROM {} WHERE hash = '{}'", CRON_TX_TABLE_NAME, hash);
+
+    let result = Storage::read(statement)?;
+    read(result)?
+        .first()
+        .cloned()
+        .ok_or(ServiceError::RecordNotFound(hash))
+  }
+
+  /**
+   * Indexed events of a cron from `from_block` on, the ones a reorg can still take back
+   */
+  pub fn get_unsettled_cron_txs(
+    &self,
+    address: String,
+    chain: String,
+    topic: String,
+    from_block: u64,
+  ) -> Result<Vec<CronTx>, ServiceError> {
+    let statement = format!(
+      "SELECT * FROM {} WHERE address = '{}' AND chain = '{}' AND topic = '{}' AND status = {} AND tx_block_number >= {} ORDER BY tx_block_number ASC",
+      CRON_TX_TABLE_NAME, address, chain, topic, CRON_TX_STATUS_SUCCESS, from_block
+    );
+
+    let result = Storage::read(statement)?;
+    read(result)
+  }
+
+  /**
+   * Moves an event to the block its transaction was mined again in
+   */
+  pub fn update_cron_tx_block(&self, hash: String, block_hash: String, tx_block_number: u64) -> Result<(), ServiceError> {
+    let statement = format!(
+      "update {} set block_hash = '{}', tx_block_number = {} where hash = '{}'",
+      CRON_TX_TABLE_NAME, block_hash, tx_block_number, hash
+    );
+
+    Storage::execute(statement)?;
+    Ok(())
+  }
+
+  pub fn revert_cron_tx(&self, hash: String, error_text: String) -> Result<(), ServiceError> {
+    let statement = format!(
+      "update {} set status = {}, error_text = '{}' where hash = '{}'",
+      CRON_TX_TABLE_NAME, CRON_TX_STATUS_REVERTED, Storage::escape(&error_text), hash
+    );
+
+    Storage::execute(statement)?;
+    Ok(())
+  }
+
   pub fn search_cron_tx(
       &self,
       address: String,
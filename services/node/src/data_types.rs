@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use marine_rs_sdk::marine;
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +10,28 @@ pub struct DataTypeClone {
   pub origin_public_key: String,
   pub origin_alias: String,
   pub origin_version: String,
+  /** Target of the copy when the request does not name a chain_id/token_address */
+  #[serde(default)]
+  pub target_data_key: String,
+  /** Attribution fields asked by the origin's fork policy */
+  #[serde(default)]
+  pub attribution: HashMap<String, String>,
+  /** Reference of the payment when the fork policy asks for a fee, required then and recorded as given */
+  #[serde(default)]
+  pub fee_transaction: String,
+}
+
+/**
+ * Answer of the origin meta contract's `on_clone`. `attribution` lists the fields the clone
+ * has to carry, `fee` is empty when forking is free, otherwise the clone has to reference a payment
+ */
+#[marine]
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ForkPolicyResult {
+  pub allowed: bool,
+  pub attribution: Vec<String>,
+  pub fee: String,
+  pub error_string: String,
 }
 
 #[marine]
@@ -5,6 +5,22 @@ pub static META_CONTRACT_TABLE_NAME: &str = "metacontracts";
 pub static CRON_TABLE_NAME: &str = "cron";
 pub static CRON_TX_TABLE_NAME: &str = "cron_tx";
 pub static TRANSACTION_RECEIPT_TABLE_NAME: &str = "transaction_receipt";
+pub static METADATA_VERSIONS_TABLE_NAME: &str = "metadata_versions";
+pub static METADATA_INDEX_PATHS_TABLE_NAME: &str = "metadata_index_paths";
+pub static METADATA_INDEX_TABLE_NAME: &str = "metadata_index";
+pub static METADATA_FTS_TABLE_NAME: &str = "metadata_fts";
+pub static METADATA_FREEZES_TABLE_NAME: &str = "metadata_freezes";
+pub static OWNERSHIP_TRANSFERS_TABLE_NAME: &str = "ownership_transfers";
+pub static GRANTS_TABLE_NAME: &str = "grants";
+pub static METADATA_PROFILES_TABLE_NAME: &str = "metadata_profiles";
+pub static ASSETS_TABLE_NAME: &str = "assets";
+pub static META_CONTRACT_VERSIONS_TABLE_NAME: &str = "meta_contract_versions";
+pub static META_CONTRACT_BINDINGS_TABLE_NAME: &str = "meta_contract_bindings";
+pub static META_CONTRACT_SUSPENSIONS_TABLE_NAME: &str = "meta_contract_suspensions";
+pub static META_CONTRACT_POLICIES_TABLE_NAME: &str = "meta_contract_policies";
+pub static CLONE_EDGES_TABLE_NAME: &str = "clone_edges";
+pub static META_CONTRACT_REGISTRATIONS_TABLE_NAME: &str = "meta_contract_registrations";
+pub static CONTRACT_RECEIPTS_TABLE_NAME: &str = "transaction_contract_receipts";
 // General Status
 pub static STATUS_PENDING: i64 = 0;
 pub static STATUS_DONE: i64 = 1;
@@ -12,21 +28,62 @@ pub static STATUS_FAILED: i64 = 1;
 // Transaction Receipt
 pub static RECEIPT_STATUS_SUCCESS: i64 = 1;
 pub static RECEIPT_STATUS_FAILED: i64 = 2;
+pub static RECEIPT_STATUS_CONFLICT: i64 = 3;
 // Cron
 pub static CRON_STATUS_ENABLE: i64 = 1;
 pub static CRON_STATUS_DISABLE: i64 = 0;
+pub static CRON_MAX_BLOCK_RANGE: u64 = 2000;
 // Cron tx
 pub static CRON_TX_STATUS_SUCCESS: i64 = 1;
 pub static CRON_TX_STATUS_FAILED: i64 = 2;
+pub static CRON_TX_STATUS_REVERTED: i64 = 3;
+pub static CRON_TX_REVERT: &str = "revert";
+pub static CRON_REORG_WINDOW: u64 = 128;
 // CRON ACTION
 pub const CRON_ACTION_CREATE: &str = "create";
 pub const CRON_ACTION_UPDATE: &str = "update";
 pub const CRON_ACTION_UPDATE_STATUS: &str = "update_status";
+// SEARCH
+pub static CONTENT_COLUMN_PREFIX: &str = "content.";
+pub static TEXT_SEARCH_PAGE_SIZE: u32 = 20;
+// TOKEN METADATA
+pub static DEFAULT_METADATA_PROFILE: &str = "default";
+// CLONE LINEAGE
+pub static CLONE_LINEAGE_MAX_DEPTH: u32 = 64;
+// PUBLISH POLICY MODES
+pub const POLICY_MODE_OPEN: &str = "open";
+pub const POLICY_MODE_ALLOWLIST: &str = "allowlist";
+pub const POLICY_MODE_DENYLIST: &str = "denylist";
+// NATIVE META CONTRACTS
+pub static NATIVE_META_CONTRACT_PREFIX: &str = "native:";
+// ASSETS
+// METADATA PATCH MODES
+pub const PATCH_MODE_MERGE: &str = "merge";
+pub const PATCH_MODE_JSON_PATCH: &str = "json-patch";
 // METHODS
 pub static METHOD_CONTRACT: &str = "contract";
 pub static METHOD_METADATA: &str = "metadata";
 pub static METHOD_CLONE: &str = "clone";
 pub static METHOD_CRON: &str = "cron";
+pub static METHOD_FREEZE: &str = "freeze";
+pub static METHOD_DELETE: &str = "delete";
+pub static METHOD_TRANSFER: &str = "transfer";
+pub static METHOD_GRANT: &str = "grant";
+pub static METHOD_UNBIND: &str = "unbind";
+pub static METHOD_SUSPEND: &str = "suspend";
+// FREEZE SCOPES
+pub const FREEZE_SCOPE_RECORD: &str = "record";
+pub const FREEZE_SCOPE_DATA_KEY: &str = "data_key";
+// TRANSFER SCOPES
+pub const TRANSFER_SCOPE_METADATA: &str = "metadata";
+pub const TRANSFER_SCOPE_META_CONTRACT: &str = "meta_contract";
+// GRANTS
+pub const GRANT_ACTION_CREATE: &str = "create";
+pub const GRANT_ACTION_REVOKE: &str = "revoke";
+pub const GRANT_SCOPE_TOKEN_KEY: &str = "token_key";
+pub const GRANT_SCOPE_DATA_KEY: &str = "data_key";
+pub const GRANT_SCOPE_ALIAS: &str = "alias";
+pub static GRANT_DELEGABLE_METHODS: [&str; 3] = ["metadata", "delete", "freeze"];
 // ENCRYPTION
 pub static ENCRYPTION_TYPE_SECP256K1: &str = "secp256k1";
 pub static ENCRYPTION_TYPE_ED25519: &str = "ed25519";
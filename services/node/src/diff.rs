@@ -0,0 +1,89 @@
+use json_patch::PatchOperation;
+use marine_rs_sdk::marine;
+use serde_json::Value;
+
+use crate::error::ServiceError;
+
+#[marine]
+#[derive(Debug, Default, Clone)]
+pub struct MetadataDiff {
+    pub patch: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+impl MetadataDiff {
+    /**
+     * RFC 6902 JSON Patch turning `from` into `to`, with the touched paths grouped by kind of change
+     */
+    pub fn between(from: &Value, to: &Value) -> Result<Self, ServiceError> {
+        let patch = json_patch::diff(from, to);
+
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        let mut changed = Vec::new();
+
+        for operation in patch.0.iter() {
+            match operation {
+                PatchOperation::Add(op) => added.push(op.path.clone()),
+                PatchOperation::Remove(op) => removed.push(op.path.clone()),
+                PatchOperation::Replace(op) => changed.push(op.path.clone()),
+                PatchOperation::Move(op) => {
+                    removed.push(op.from.clone());
+                    added.push(op.path.clone());
+                }
+                PatchOperation::Copy(op) => added.push(op.path.clone()),
+                PatchOperation::Test(_) => {}
+            }
+        }
+
+        Ok(Self {
+            patch: serde_json::to_string(&patch)?,
+            added,
+            removed,
+            changed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn groups_paths_by_kind_of_change() {
+        let from = json!({"name": "a", "color": "red", "size": 1});
+        let to = json!({"name": "b", "size": 1, "rarity": "rare"});
+
+        let diff = MetadataDiff::between(&from, &to).unwrap();
+
+        assert_eq!(diff.added, vec!["/rarity"]);
+        assert_eq!(diff.removed, vec!["/color"]);
+        assert_eq!(diff.changed, vec!["/name"]);
+    }
+
+    #[test]
+    fn patch_turns_from_into_to() {
+        let from = json!({"traits": [{"type": "eyes"}], "level": 1});
+        let to = json!({"traits": [{"type": "eyes"}, {"type": "hat"}], "level": 2});
+
+        let diff = MetadataDiff::between(&from, &to).unwrap();
+        let patch: json_patch::Patch = serde_json::from_str(&diff.patch).unwrap();
+
+        let mut patched = from.clone();
+        json_patch::patch(&mut patched, &patch).unwrap();
+        assert_eq!(patched, to);
+    }
+
+    #[test]
+    fn same_content_has_no_changes() {
+        let content = json!({"name": "a", "nested": {"value": [1, 2]}});
+
+        let diff = MetadataDiff::between(&content, &content).unwrap();
+
+        assert_eq!(diff.patch, "[]");
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty());
+    }
+}
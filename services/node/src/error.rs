@@ -32,6 +32,24 @@ pub enum ServiceError {
     InvalidDataFormatForMethodType(String),
     #[error["No Meta Contract ID specify"]]
     NoProgramId(),
+    #[error["Invalid patch: {0}"]]
+    InvalidPatch(String),
+    #[error["Conflict: {0}"]]
+    Conflict(String),
+    #[error["Metadata frozen: {0}"]]
+    Frozen(String),
+    #[error["Metadata deleted: {0}"]]
+    Deleted(String),
+    #[error["Invalid grant: {0}"]]
+    InvalidGrant(String),
+    #[error["Invalid asset id: {0}"]]
+    InvalidAssetId(String),
+    #[error["Meta contract suspended: {0}"]]
+    Suspended(String),
+    #[error["Not allowed by meta contract policy: {0}"]]
+    NotAllowed(String),
+    #[error["Fork denied: {0}"]]
+    ForkDenied(String),
 }
 
 impl From<SerdeJsonError> for ServiceError {
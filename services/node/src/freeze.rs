@@ -0,0 +1,22 @@
+use serde::Deserialize;
+
+/**
+ * Lock placed on a metadata record, or on every record of a data_key when `scope` is "data_key".
+ * Record fields are empty for a data_key freeze
+ */
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MetadataFreeze {
+    pub scope: String,
+    pub data_key: String,
+    pub meta_contract_id: String,
+    pub public_key: String,
+    pub alias: String,
+    pub version: String,
+    pub transaction_hash: String,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct SerdeFreeze {
+    pub scope: String,
+}
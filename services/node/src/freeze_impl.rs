@@ -0,0 +1,97 @@
+use crate::defaults::{FREEZE_SCOPE_DATA_KEY, METADATA_FREEZES_TABLE_NAME};
+use crate::error::ServiceError;
+use crate::error::ServiceError::InternalError;
+use crate::freeze::MetadataFreeze;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+
+impl Storage {
+    pub fn create_metadata_freezes_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                scope TEXT not null,
+                data_key TEXT not null,
+                meta_contract_id TEXT not null,
+                public_key TEXT not null,
+                alias TEXT not null,
+                version TEXT not null,
+                transaction_hash TEXT not null,
+                timestamp INTEGER not null,
+                PRIMARY KEY (data_key, meta_contract_id, public_key, alias, version)
+            )",
+            METADATA_FREEZES_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_metadata_freezes_table error: {}", error);
+        }
+    }
+
+    pub fn write_metadata_freeze(&self, freeze: MetadataFreeze) -> Result<(), ServiceError> {
+        let s = format!(
+            "insert into {} (scope, data_key, meta_contract_id, public_key, alias, version, transaction_hash, timestamp) values ('{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}')",
+            METADATA_FREEZES_TABLE_NAME,
+            freeze.scope,
+            freeze.data_key,
+            freeze.meta_contract_id,
+            freeze.public_key,
+            freeze.alias,
+            freeze.version,
+            freeze.transaction_hash,
+            freeze.timestamp,
+        );
+
+        let result = Storage::execute(s);
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                log::info!("{}", e);
+                Err(InternalError(e.to_string()))
+            }
+        }
+    }
+
+    /**
+     * Freeze covering a metadata record, either on the record itself or on its whole data_key.
+     * A data_key freeze holds for every meta contract bound to the token
+     */
+    pub fn get_metadata_freeze(
+        &self,
+        data_key: String,
+        meta_contract_id: String,
+        public_key: String,
+        alias: String,
+        version: String,
+    ) -> Result<Option<MetadataFreeze>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE data_key = '{}' AND (scope = '{}' OR (meta_contract_id = '{}' AND public_key = '{}' AND alias = '{}' AND version = '{}')) ORDER BY timestamp LIMIT 1",
+            METADATA_FREEZES_TABLE_NAME, data_key, FREEZE_SCOPE_DATA_KEY, meta_contract_id, public_key, alias, version
+        );
+
+        let result = Storage::read(statement)?;
+        Ok(read(result)?.first().cloned())
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<MetadataFreeze>, ServiceError> {
+    let mut freezes = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::MetadataFreeze(freeze) => freezes.push(freeze),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        METADATA_FREEZES_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(freezes)
+}
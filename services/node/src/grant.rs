@@ -0,0 +1,140 @@
+use marine_rs_sdk::marine;
+use serde::Deserialize;
+
+use crate::defaults::{GRANT_SCOPE_ALIAS, GRANT_SCOPE_DATA_KEY, GRANT_SCOPE_TOKEN_KEY};
+
+/**
+ * Write permission delegated by `grantor` to `grantee`.
+ * `methods` is a comma separated list, `expiry` 0 never expires and `revoked` holds the revoking transaction
+ */
+#[marine]
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Grant {
+    pub hash: String,
+    pub grantor: String,
+    pub grantee: String,
+    pub scope: String,
+    pub token_key: String,
+    pub data_key: String,
+    pub meta_contract_id: String,
+    pub alias: String,
+    pub methods: String,
+    pub expiry: u64,
+    pub revoked: String,
+    pub timestamp: u64,
+}
+
+impl Grant {
+    pub fn allows(&self, method: &str) -> bool {
+        self.methods.split(',').any(|m| m == method)
+    }
+
+    /**
+     * Whether the grant covers a record, the scope decides how much of the key has to match
+     */
+    pub fn covers(&self, token_key: &str, data_key: &str, meta_contract_id: &str, alias: &str) -> bool {
+        if !self.meta_contract_id.is_empty() && self.meta_contract_id != meta_contract_id {
+            return false;
+        }
+
+        match self.scope.as_str() {
+            GRANT_SCOPE_TOKEN_KEY => self.token_key == token_key,
+            GRANT_SCOPE_DATA_KEY => self.data_key == data_key,
+            GRANT_SCOPE_ALIAS => self.data_key == data_key && self.alias == alias,
+            _ => false,
+        }
+    }
+
+    pub fn is_active(&self, at: u64) -> bool {
+        self.revoked.is_empty() && (self.expiry == 0 || self.expiry > at)
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct SerdeGrant {
+    pub action: String,
+    pub grantee: String,
+    pub scope: String,
+    pub methods: Vec<String>,
+    pub expiry: u64,
+    pub grant_hash: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant(scope: &str) -> Grant {
+        Grant {
+            scope: scope.to_string(),
+            token_key: "token".to_string(),
+            data_key: "data".to_string(),
+            alias: "main".to_string(),
+            methods: "metadata,delete".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn allows_listed_methods_only() {
+        let grant = grant(GRANT_SCOPE_TOKEN_KEY);
+
+        assert!(grant.allows("metadata"));
+        assert!(grant.allows("delete"));
+        assert!(!grant.allows("freeze"));
+        assert!(!grant.allows("meta"));
+    }
+
+    #[test]
+    fn token_key_scope_covers_the_collection() {
+        let grant = grant(GRANT_SCOPE_TOKEN_KEY);
+
+        assert!(grant.covers("token", "other", "mc", "other"));
+        assert!(!grant.covers("other", "data", "mc", "main"));
+    }
+
+    #[test]
+    fn data_key_scope_covers_every_alias_of_a_token() {
+        let grant = grant(GRANT_SCOPE_DATA_KEY);
+
+        assert!(grant.covers("token", "data", "mc", "other"));
+        assert!(!grant.covers("token", "other", "mc", "main"));
+    }
+
+    #[test]
+    fn alias_scope_covers_one_record() {
+        let grant = grant(GRANT_SCOPE_ALIAS);
+
+        assert!(grant.covers("token", "data", "mc", "main"));
+        assert!(!grant.covers("token", "data", "mc", "other"));
+    }
+
+    #[test]
+    fn restricts_to_its_meta_contract() {
+        let mut grant = grant(GRANT_SCOPE_TOKEN_KEY);
+        grant.meta_contract_id = "mc".to_string();
+
+        assert!(grant.covers("token", "data", "mc", "main"));
+        assert!(!grant.covers("token", "data", "other", "main"));
+    }
+
+    #[test]
+    fn unknown_scope_covers_nothing() {
+        assert!(!grant("everything").covers("token", "data", "mc", "main"));
+    }
+
+    #[test]
+    fn expires_and_revokes() {
+        let mut grant = grant(GRANT_SCOPE_TOKEN_KEY);
+        assert!(grant.is_active(100));
+
+        grant.expiry = 100;
+        assert!(grant.is_active(99));
+        assert!(!grant.is_active(100));
+
+        grant.expiry = 0;
+        grant.revoked = "0xrevoke".to_string();
+        assert!(!grant.is_active(0));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::defaults::GRANTS_TABLE_NAME;
+use crate::error::ServiceError;
+use crate::error::ServiceError::InternalError;
+use crate::grant::Grant;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+
+impl Storage {
+    pub fn create_grants_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                hash TEXT not null primary key,
+                grantor TEXT not null,
+                grantee TEXT not null,
+                scope TEXT not null,
+                token_key TEXT not null,
+                data_key TEXT not null,
+                meta_contract_id TEXT not null,
+                alias TEXT not null,
+                methods TEXT not null,
+                expiry INTEGER not null,
+                revoked TEXT not null default '',
+                timestamp INTEGER not null
+            )",
+            GRANTS_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_grants_table error: {}", error);
+        }
+    }
+
+    pub fn write_grant(&self, grant: Grant) -> Result<(), ServiceError> {
+        let s = format!(
+            "insert into {} (hash, grantor, grantee, scope, token_key, data_key, meta_contract_id, alias, methods, expiry, revoked, timestamp) values ('{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}')",
+            GRANTS_TABLE_NAME,
+            grant.hash,
+            grant.grantor,
+            grant.grantee,
+            grant.scope,
+            grant.token_key,
+            grant.data_key,
+            grant.meta_contract_id,
+            grant.alias,
+            grant.methods,
+            grant.expiry,
+            grant.revoked,
+            grant.timestamp,
+        );
+
+        let result = Storage::execute(s);
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                log::info!("{}", e);
+                Err(InternalError(e.to_string()))
+            }
+        }
+    }
+
+    pub fn revoke_grant(&self, hash: String, transaction_hash: String) -> Result<(), ServiceError> {
+        let s = format!(
+            "update {} set revoked = '{}' where hash = '{}'",
+            GRANTS_TABLE_NAME, transaction_hash, hash
+        );
+
+        Storage::execute(s)?;
+        Ok(())
+    }
+
+    pub fn get_grant(&self, hash: String) -> Result<Grant, ServiceError> {
+        let statement = format!("SELECT * FROM {} WHERE hash = '{}'", GRANTS_TABLE_NAME, hash);
+
+        let result = Storage::read(statement)?;
+        match read(result) {
+            Ok(grants) => grants
+                .first()
+                .cloned()
+                .ok_or_else(|| ServiceError::RecordNotFound(format!("No grant {}", hash))),
+            Err(e) => Err(e),
+        }
+    }
+
+    /**
+     * Grants given by or to a public key, newest first
+     */
+    pub fn get_grants(&self, public_key: String) -> Result<Vec<Grant>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE grantor = '{}' OR grantee = '{}' ORDER BY timestamp DESC",
+            GRANTS_TABLE_NAME, public_key, public_key
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<Grant>, ServiceError> {
+    let mut grants = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::Grant(grant) => grants.push(grant),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        GRANTS_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(grants)
+}
@@ -1,54 +1,109 @@
 #![allow(improper_ctypes)]
 
+mod asset;
+mod asset_impl;
 mod block;
+mod clone_edge;
+mod clone_edge_impl;
 pub mod cron;
 pub mod cron_tx;
 mod data_types;
 mod defaults;
+mod diff;
 mod error;
+mod freeze;
+mod freeze_impl;
+mod grant;
+mod grant_impl;
 mod meta_contract;
+mod meta_contract_bindings;
+mod meta_contract_bindings_impl;
 mod meta_contract_impl;
+mod meta_contract_versions;
+mod meta_contract_versions_impl;
+mod metadata_fts;
+mod metadata_fts_impl;
+mod metadata_index;
+mod metadata_index_impl;
+mod metadata_versions;
+mod metadata_versions_impl;
 mod metadatas;
 mod metadatas_impl;
+mod native_policy;
+mod normalize;
+mod normalize_impl;
+mod patch;
+mod policy;
+mod policy_impl;
+mod registration;
+mod registration_impl;
 mod result;
 mod storage_impl;
+mod suspension;
+mod suspension_impl;
+mod token_metadata;
+mod token_metadata_impl;
 mod transaction;
 mod transaction_receipt;
+mod transfer;
+mod transfer_impl;
 pub mod transactions_impl;
 mod validators;
 
+use asset::Asset;
 use cron::SerdeCron;
+use block::Block;
 use cron_tx::CronTx;
-use data_types::{DataTypeClone, DataTypeFork, SerdeDataTypeFork};
+use data_types::{DataTypeClone, DataTypeFork, ForkPolicyResult, SerdeDataTypeFork};
+use diff::MetadataDiff;
+use freeze::SerdeFreeze;
+use grant::{Grant, SerdeGrant};
 use defaults::{
     CRON_ACTION_CREATE, CRON_STATUS_ENABLE, CRON_STATUS_DISABLE, ENCRYPTION_TYPE_ED25519,
     ENCRYPTION_TYPE_SECP256K1, METHOD_CRON, STATUS_PENDING, STATUS_DONE, CRON_TX_STATUS_FAILED, CRON_TX_STATUS_SUCCESS,
+    CRON_TX_STATUS_REVERTED, CRON_TX_REVERT, CRON_REORG_WINDOW,
 };
-use defaults::{METHOD_CLONE, METHOD_CONTRACT, METHOD_METADATA, RECEIPT_STATUS_FAILED};
+use defaults::{METHOD_CLONE, METHOD_CONTRACT, METHOD_DELETE, METHOD_FREEZE, METHOD_METADATA, RECEIPT_STATUS_FAILED, TEXT_SEARCH_PAGE_SIZE};
+use defaults::{CLONE_LINEAGE_MAX_DEPTH, DEFAULT_METADATA_PROFILE, METHOD_SUSPEND, METHOD_UNBIND};
+use defaults::{POLICY_MODE_ALLOWLIST, POLICY_MODE_DENYLIST, POLICY_MODE_OPEN};
+use defaults::{GRANT_ACTION_CREATE, GRANT_ACTION_REVOKE, GRANT_DELEGABLE_METHODS, GRANT_SCOPE_ALIAS, GRANT_SCOPE_DATA_KEY, GRANT_SCOPE_TOKEN_KEY, METHOD_GRANT};
+use defaults::{FREEZE_SCOPE_DATA_KEY, FREEZE_SCOPE_RECORD, METHOD_TRANSFER, TRANSFER_SCOPE_META_CONTRACT, TRANSFER_SCOPE_METADATA};
 use marine_rs_sdk::{marine, MountedBinaryResult};
 use marine_rs_sdk::module_manifest;
 use marine_rs_sdk::WasmLoggerBuilder;
 
 use error::ServiceError::{
-    self, InvalidMethod, InvalidOwner, InvalidSignature, NoEncryptionType,
+    self, Deleted, Frozen, NotAllowed, Suspended, InvalidGrant, InvalidMethod, InvalidOwner, InvalidSignature, NoEncryptionType,
     NotSupportedEncryptionType, RecordFound,
 };
 
-use meta_contract::MetaContract;
-use metadatas::{FinalMetadata, MetadataOrdering, MetadataQuery, Metadata};
+use meta_contract::{MetaContract, SerdeMetaContract};
+use metadata_versions::MetadataVersion;
+use metadatas::{FinalMetadata, MetadataContent, MetadataOrdering, MetadataQuery, Metadata, SerdeDelete};
+use patch::{inherit, patch_mode, validate_patch};
 use result::{
-    FdbClock, FdbCronTxResult, FdbCronTxsResult, FdbCronsResult, FdbMetaContractResult,
-    FdbMetadataHistoryResult, FdbMetadatasResult, FdbTransactionResult, FdbTransactionsResult,
+    FdbClock, FdbCronResult, FdbCronRangeResult, FdbCronTxResult, FdbCronTxsResult, FdbCronsResult, FdbMetaContractResult,
+    FdbMetadataDiffResult, FdbMetadataTextSearchResult, FdbOwnershipTransfersResult, FdbGrantsResult, FdbMetadataContentResult,
+    FdbTokenMetadataResult, FdbMigrationResult, FdbAssetResult, FdbMetaContractVersionsResult,
+    FdbMetaContractBindingsResult, FdbMetaContractsResult, FdbMetaContractSuspensionResult,
+    FdbMetaContractPolicyResult, FdbCloneEdgesResult,
+    FdbMetadataHistoryResult, FdbMetadataVersionResult, FdbMetadataVersionsResult, FdbMetadatasResult, FdbTransactionResult,
+    FdbTransactionsResult, FdbTransactionReceiptsResult,
 };
 use result::{FdbMetadataResult, FdbResult};
-use serde_json::Value;
+use std::cmp::max;
 use std::time::{SystemTime, UNIX_EPOCH};
 use storage_impl::get_storage;
 use transaction::{Transaction, TransactionQuery, TransactionOrdering, TransactionRequest, TransactionReceipt};
-use types::{IpfsDagGetResult, IpfsDagPutResult};
+use native_policy::{is_native, native_policy};
+use policy::SerdePolicy;
+use suspension::SerdeSuspend;
+use transfer::{SerdeTransfer, SerdeTransferConsent};
+use types::{IpfsDagGetResult, IpfsDagPutResult, IpfsUnpinResult};
 use validators::{
-    validate_clone, validate_cron, validate_meta_contract, validate_metadata,
-    validate_metadata_cron,
+    validate_clone, validate_cron, validate_delete, validate_freeze, validate_meta_contract, validate_metadata,
+    validate_grant, validate_metadata_cron, validate_transfer, validate_unbind,
+    validate_suspend, validate_metadata_native, validate_clone_native, validate_clone_failed,
 };
 
 #[macro_use]
@@ -79,6 +134,42 @@ pub fn init() {
   storage.create_cron_table();
   storage.create_cron_tx_table();
   storage.create_transaction_receipt_table();
+  storage.create_metadata_versions_table();
+  storage.create_metadata_index_tables();
+  storage.create_metadata_fts_table();
+  storage.create_metadata_freezes_table();
+  storage.create_ownership_transfers_table();
+  storage.create_grants_table();
+  storage.create_metadata_profiles_table();
+  storage.create_assets_table();
+  storage.create_meta_contract_versions_table();
+  storage.create_meta_contract_bindings_table();
+  storage.create_meta_contract_suspensions_table();
+  storage.create_meta_contract_policies_table();
+  storage.create_clone_edges_table();
+  storage.create_meta_contract_registrations_table();
+
+  // records keyed before normalization move to their normalized keys, a no-op once they did
+  if let Err(error) = storage.migrate_normalized_keys() {
+    println!("migrate_normalized_keys error: {}", error);
+  }
+
+  if let Err(error) = storage.backfill_assets() {
+    println!("backfill_assets error: {}", error);
+  }
+
+  // records that predate the versions table start their history at their current CID
+  if let Err(error) = storage.backfill_metadata_versions() {
+    println!("backfill_metadata_versions error: {}", error);
+  }
+
+  if let Err(error) = storage.backfill_metadata_text() {
+    println!("backfill_metadata_text error: {}", error);
+  }
+
+  if let Err(error) = storage.backfill_clone_edges() {
+    println!("backfill_clone_edges error: {}", error);
+  }
 }
 
 #[marine]
@@ -95,7 +186,9 @@ pub fn publish(
     // version: i64,
     tx_request: TransactionRequest
 ) -> FdbResult {
-    let mut meta_contract_id = tx_request.meta_contract_id;
+    let mut tx_request = tx_request;
+    let mut meta_contract_id = tx_request.meta_contract_id.clone();
+    let signer = tx_request.public_key.clone();
     let mut data_key = "".to_string();
     let mut token_key = "".to_string();
     let mut content = "".to_string();
@@ -107,6 +200,12 @@ pub fn publish(
             && tx_request.method != METHOD_METADATA
             && tx_request.method != METHOD_CLONE
             && tx_request.method != METHOD_CRON
+            && tx_request.method != METHOD_FREEZE
+            && tx_request.method != METHOD_DELETE
+            && tx_request.method != METHOD_TRANSFER
+            && tx_request.method != METHOD_GRANT
+            && tx_request.method != METHOD_UNBIND
+            && tx_request.method != METHOD_SUSPEND
         {
             error = Some(InvalidMethod(f!("invalid method: {tx_request.method}")));
         }
@@ -117,10 +216,42 @@ pub fn publish(
         error = Some(ServiceError::InvalidEncryption(tx_request.public_key.clone()));
     }
 
+    let mut asset: Option<Asset> = None;
+    if error.is_none() && !tx_request.asset_id.is_empty() {
+        match resolve_asset_id(&mut tx_request) {
+            Ok(resolved) => asset = Some(resolved),
+            Err(e) => error = Some(e),
+        }
+    }
+
+    // a grantee signs for the grantor, every ownership check below runs against the grantor.
+    // The record it covers is known once the asset_id is resolved
+    if error.is_none() && !tx_request.grant_hash.is_empty() {
+        match check_grant_use(&tx_request) {
+            Ok(grant) => tx_request.public_key = grant.grantor,
+            Err(e) => error = Some(e),
+        }
+    }
+
     if error.is_none() {
         if tx_request.method.clone() == METHOD_METADATA {
           if meta_contract_id.clone().is_empty() {
               error = Some(ServiceError::NoProgramId());
+          } else if is_native(&meta_contract_id) && native_policy(&meta_contract_id).is_none() {
+              error = Some(ServiceError::RecordNotFound(f!("native meta contract {meta_contract_id}")));
+          }
+
+          if error.is_none() {
+            if let Err(e) = check_publish_access(&meta_contract_id, &tx_request.public_key, &tx_request.alias) {
+              error = Some(e);
+            }
+          }
+
+          // a key the policy keeps out cannot get in through someone else's grant
+          if error.is_none() && signer != tx_request.public_key {
+            if let Err(e) = check_publish_access(&meta_contract_id, &signer, &tx_request.alias) {
+              error = Some(e);
+            }
           }
 
           if error.is_none() {
@@ -132,6 +263,16 @@ pub fn publish(
 
             token_key = Metadata::generate_token_key(tx_request.chain_id.clone(), tx_request.token_address.clone());
 
+            match patch_mode(&tx_request.mcdata) {
+                Ok(mode) if !mode.is_empty() => {
+                    if let Err(e) = validate_patch(&mode, &tx_request.data) {
+                        error = Some(e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => error = Some(e),
+            }
+
             let result = storage.get_owner_metadata(
               data_key.clone(),
               meta_contract_id.clone(),
@@ -144,6 +285,8 @@ pub fn publish(
                 Ok(metadata) => {
                   if metadata.public_key != tx_request.public_key.clone() {
                       error = Some(InvalidOwner(f!("not owner of data_key: {tx_request.public_key}")));
+                  } else if !metadata.tombstone.is_empty() {
+                      error = Some(Deleted(metadata.tombstone));
                   }
 
                   content = metadata.cid;
@@ -151,6 +294,119 @@ pub fn publish(
                 Err(ServiceError::RecordNotFound(_)) => {}
                 Err(e) => error = Some(e),
             }
+
+            if error.is_none() {
+              match storage.get_metadata_freeze(
+                data_key.clone(),
+                meta_contract_id.clone(),
+                tx_request.public_key.clone(),
+                tx_request.alias.clone(),
+                tx_request.version.clone(),
+              ) {
+                Ok(Some(freeze)) => error = Some(Frozen(f!("frozen by {freeze.transaction_hash}"))),
+                Ok(None) => {}
+                Err(e) => error = Some(e),
+              }
+            }
+          }
+        } else if tx_request.method.clone() == METHOD_FREEZE {
+          if meta_contract_id.clone().is_empty() {
+              error = Some(ServiceError::NoProgramId());
+          }
+
+          if error.is_none() {
+            data_key = Metadata::generate_data_key(
+              tx_request.chain_id.clone(),
+              tx_request.token_address.clone(),
+              tx_request.token_id.clone(),
+            );
+
+            token_key = Metadata::generate_token_key(tx_request.chain_id.clone(), tx_request.token_address.clone());
+
+            if let Err(e) = check_freeze_request(&tx_request.data, &tx_request.public_key, &tx_request.alias, &tx_request.version, &data_key, &token_key, &meta_contract_id) {
+              error = Some(e);
+            }
+          }
+        } else if tx_request.method.clone() == METHOD_DELETE {
+          if meta_contract_id.clone().is_empty() {
+              error = Some(ServiceError::NoProgramId());
+          }
+
+          if error.is_none() {
+            data_key = Metadata::generate_data_key(
+              tx_request.chain_id.clone(),
+              tx_request.token_address.clone(),
+              tx_request.token_id.clone(),
+            );
+
+            token_key = Metadata::generate_token_key(tx_request.chain_id.clone(), tx_request.token_address.clone());
+
+            if let Err(e) = serde_json::from_str::<SerdeDelete>(&tx_request.data) {
+              error = Some(ServiceError::InvalidDataFormatForMethodType(e.to_string()));
+            }
+          }
+
+          if error.is_none() {
+            let result = storage.get_owner_metadata(
+              data_key.clone(),
+              meta_contract_id.clone(),
+              tx_request.public_key.clone(),
+              tx_request.alias.clone(),
+              tx_request.version.clone(),
+            );
+
+            match result {
+                Ok(metadata) => {
+                  if !metadata.tombstone.is_empty() {
+                      error = Some(Deleted(metadata.tombstone));
+                  }
+
+                  content = metadata.cid;
+                }
+                Err(e) => error = Some(e),
+            }
+          }
+
+          if error.is_none() {
+            match storage.get_metadata_freeze(
+              data_key.clone(),
+              meta_contract_id.clone(),
+              tx_request.public_key.clone(),
+              tx_request.alias.clone(),
+              tx_request.version.clone(),
+            ) {
+              Ok(Some(freeze)) => error = Some(Frozen(f!("frozen by {freeze.transaction_hash}"))),
+              Ok(None) => {}
+              Err(e) => error = Some(e),
+            }
+          }
+        } else if tx_request.method.clone() == METHOD_TRANSFER {
+          if !tx_request.chain_id.is_empty() && !tx_request.token_address.is_empty() {
+            data_key = Metadata::generate_data_key(
+              tx_request.chain_id.clone(),
+              tx_request.token_address.clone(),
+              tx_request.token_id.clone(),
+            );
+
+            token_key = Metadata::generate_token_key(tx_request.chain_id.clone(), tx_request.token_address.clone());
+          }
+
+          if let Err(e) = check_transfer_request(&tx_request, &data_key, &token_key) {
+            error = Some(e);
+          }
+        } else if tx_request.method.clone() == METHOD_GRANT {
+          if !tx_request.chain_id.is_empty() && !tx_request.token_address.is_empty() {
+            data_key = Metadata::generate_data_key(
+              tx_request.chain_id.clone(),
+              tx_request.token_address.clone(),
+              tx_request.token_id.clone(),
+            );
+
+            token_key = Metadata::generate_token_key(tx_request.chain_id.clone(), tx_request.token_address.clone());
+          }
+
+          if let Err(e) = check_grant_request(&tx_request, &token_key) {
+            error = Some(e);
           }
         } else if tx_request.method.clone() == METHOD_CONTRACT {
             if tx_request.data.is_empty() {
@@ -171,8 +427,41 @@ pub fn publish(
                     Err(e) => error = Some(e),
                   }
               }
+
+              if !tx_request.mcdata.is_empty() {
+                match serde_json::from_str::<SerdeMetaContract>(&tx_request.mcdata) {
+                  Ok(config) => {
+                    if let Err(e) = check_config_request(&tx_request, &config) {
+                      error = Some(e);
+                    }
+                  }
+                  Err(e) => error = Some(ServiceError::InvalidDataFormatForMethodType(e.to_string())),
+                }
+              }
+              if is_native(&tx_request.data) && native_policy(&tx_request.data).is_none() {
+                error = Some(ServiceError::RecordNotFound(f!("native meta contract {tx_request.data}")));
+              }
               meta_contract_id = tx_request.data.clone();
             }
+        } else if tx_request.method.clone() == METHOD_SUSPEND {
+            if meta_contract_id.is_empty() {
+              error = Some(ServiceError::NoProgramId());
+            } else if is_native(&meta_contract_id) {
+              error = Some(NotAllowed(f!("reserved meta contract {meta_contract_id} cannot be suspended")));
+            } else if let Err(e) = serde_json::from_str::<SerdeSuspend>(&tx_request.data) {
+              error = Some(ServiceError::InvalidDataFormatForMethodType(e.to_string()));
+            } else if !is_meta_contract_owner(&meta_contract_id, &tx_request.public_key) {
+              error = Some(InvalidOwner(f!("not owner of meta contract: {tx_request.public_key}")));
+            }
+        } else if tx_request.method.clone() == METHOD_UNBIND {
+            token_key = Metadata::generate_token_key(tx_request.chain_id.clone(), tx_request.token_address.clone());
+
+            if is_native(&tx_request.data) {
+              error = Some(NotAllowed(f!("reserved meta contract {tx_request.data} cannot be unbound")));
+            } else if let Err(e) = check_unbind_request(&tx_request, &token_key) {
+              error = Some(e);
+            }
+            meta_contract_id = tx_request.data.clone();
         } else if tx_request.method.clone() == METHOD_CLONE {
             let data_clone_result: Result<DataTypeClone, serde_json::Error> =
                 serde_json::from_str(&tx_request.data.clone());
@@ -188,16 +477,73 @@ pub fn publish(
                   );
 
                     match origin_metadata_result {
-                        Ok(_) => {}
+                        Ok(origin) => {
+                          // the copy belongs to the signer, under the alias of the request
+                          if tx_request.alias.is_empty() {
+                              tx_request.alias = origin.alias;
+                          }
+
+                          if !tx_request.token_address.is_empty() {
+                              data_key = Metadata::generate_data_key(
+                                tx_request.chain_id.clone(),
+                                tx_request.token_address.clone(),
+                                tx_request.token_id.clone(),
+                              );
+                              token_key = Metadata::generate_token_key(tx_request.chain_id.clone(), tx_request.token_address.clone());
+                          } else if !data_clone.target_data_key.is_empty() {
+                              // the target may sit in another collection, its token_key comes from its own asset
+                              let target = Asset::resolve_data_key(&data_clone.target_data_key)
+                                  .and_then(|target| storage.get_asset(target));
+
+                              match target {
+                                  Ok(asset) => {
+                                      data_key = asset.data_key;
+                                      token_key = Metadata::generate_token_key(asset.chain_id, asset.token_address);
+                                  }
+                                  Err(ServiceError::RecordNotFound(target)) => {
+                                      error = Some(ServiceError::RecordNotFound(f!("unknown target data_key: {target}")))
+                                  }
+                                  Err(e) => error = Some(e),
+                              }
+                          }
+
+                          if error.is_none() && data_key.is_empty() {
+                              error = Some(ServiceError::InvalidDataFormatForMethodType(f!("{tx_request.method}: no target")));
+                          }
+
+                          // the copy is written under the origin's meta contract, by the signer
+                          if error.is_none() {
+                              if let Err(e) = check_publish_access(&data_clone.origin_meta_contract_id, &tx_request.public_key, &tx_request.alias) {
+                                  error = Some(e);
+                              }
+                          }
+                        }
                         Err(e) => error = Some(e),
                     }
 
+                    // a frozen target data_key takes no copies either
+                    if error.is_none() {
+                      let freeze = storage.get_metadata_freeze(
+                          data_key.clone(),
+                          data_clone.origin_meta_contract_id.clone(),
+                          tx_request.public_key.clone(),
+                          tx_request.alias.clone(),
+                          data_clone.origin_version.clone(),
+                      );
+
+                      match freeze {
+                          Ok(Some(freeze)) => error = Some(Frozen(f!("{data_key} frozen by {freeze.transaction_hash}"))),
+                          Ok(None) => {}
+                          Err(e) => error = Some(e),
+                      }
+                    }
+
                     if error.is_none() {
                       let new_metadata_result = storage.get_owner_metadata(
                           data_key.clone(),
                           data_clone.origin_meta_contract_id.clone(),
-                          data_clone.origin_public_key.clone(),
-                          data_clone.origin_alias.clone(),
+                          tx_request.public_key.clone(),
+                          tx_request.alias.clone(),
                           data_clone.origin_version.clone(),
                       );
 
@@ -222,6 +568,7 @@ pub fn publish(
                             || serde_cron.chain.len() <= 0
                             || serde_cron.topic.len() <= 0
                             || serde_cron.token_type.len() <= 0
+                            || (serde_cron.end_block > 0 && serde_cron.end_block < serde_cron.start_block)
                         {
                             error =
                                 Some(ServiceError::InvalidDataFormatForMethodType(tx_request.method.clone()))
@@ -277,14 +624,24 @@ pub fn publish(
 
     if error.is_none() {
         let v = verify(
-            tx_request.public_key.clone(),
+            signer.clone(),
             tx_request.signature.clone(),
             tx_request.data.clone(),
             enc_verify.clone(),
         );
 
         if !v {
-            error = Some(InvalidSignature(f!("not owner of data_key: {tx_request.public_key}")));
+            error = Some(InvalidSignature(f!("not owner of data_key: {signer}")));
+        }
+    }
+
+    // keeps the data_key reversible to the token it was derived from
+    if error.is_none() && !data_key.is_empty() {
+        // the namespace is only known from an explicit asset_id, the token standard is not guessed
+        let asset = asset.unwrap_or_else(|| Asset::new(&tx_request.chain_id, "", &tx_request.token_address, &tx_request.token_id));
+
+        if asset.data_key == data_key {
+            let _ = storage.write_asset(asset);
         }
     }
 
@@ -309,6 +666,8 @@ pub fn publish(
         tx_request.mcdata,
         0,
         content,
+        tx_request.expected_cid,
+        tx_request.grant_hash,
     );
 
     let _ = storage.write_transaction(transaction.clone());
@@ -322,6 +681,7 @@ pub fn publish(
           timestamp: timestamp.as_millis() as u64,
           error_text: error.unwrap().to_string(),
           data: "".to_string(),
+          meta_contract_version: 0,
         };
         storage.write_transaction_receipt(receipt);
     }
@@ -332,6 +692,359 @@ pub fn publish(
     }
 }
 
+/**
+ * Fills the chain id, address and token id of a request from its CAIP-19 asset id.
+ * Coordinates sent alongside it must name the same asset
+ */
+fn resolve_asset_id(tx_request: &mut TransactionRequest) -> Result<Asset, ServiceError> {
+    let asset = Asset::parse(&tx_request.asset_id)?;
+
+    let given = Asset::new(&tx_request.chain_id, &asset.namespace, &tx_request.token_address, &tx_request.token_id);
+    if (!tx_request.chain_id.is_empty() && given.chain_id != asset.chain_id)
+        || (!tx_request.token_address.is_empty() && given.token_address != asset.token_address)
+        || (!tx_request.token_id.is_empty() && given.token_id != asset.token_id)
+    {
+        return Err(ServiceError::InvalidAssetId(f!("{tx_request.asset_id} does not match the request coordinates")));
+    }
+
+    tx_request.chain_id = asset.chain_id.clone();
+    tx_request.token_address = asset.token_address.clone();
+    tx_request.token_id = asset.token_id.clone();
+
+    Ok(asset)
+}
+
+/**
+ * A policy needs a known mode
+ */
+fn check_policy_request(policy: &SerdePolicy) -> Result<(), ServiceError> {
+    match policy.mode.as_str() {
+        POLICY_MODE_OPEN | POLICY_MODE_ALLOWLIST | POLICY_MODE_DENYLIST => Ok(()),
+        _ => Err(ServiceError::InvalidDataFormatForMethodType(f!("unknown policy mode: {policy.mode}"))),
+    }
+}
+
+/**
+ * Indexed paths, profiles and policy are shared by every collection bound to the meta contract,
+ * only its registrant sets them and reserved native ids have none
+ */
+fn check_config_request(tx_request: &TransactionRequest, config: &SerdeMetaContract) -> Result<(), ServiceError> {
+    if let Some(policy) = &config.policy {
+        check_policy_request(policy)?;
+    }
+
+    if config.indexed_paths.is_none() && config.profiles.is_none() && config.policy.is_none() {
+        return Ok(());
+    }
+
+    if is_native(&tx_request.data) {
+        return Err(NotAllowed(f!("reserved meta contract {tx_request.data} has no settings")));
+    }
+
+    // a contract nobody registered yet is registered by this transaction
+    let registered = get_storage().get_meta_contract_registration(tx_request.data.clone())?.is_some();
+
+    if registered && !is_meta_contract_owner(&tx_request.data, &tx_request.public_key) {
+        return Err(InvalidOwner(f!("not owner of meta contract: {tx_request.public_key}")));
+    }
+
+    Ok(())
+}
+
+/**
+ * A suspended meta contract takes no writes, and its policy decides who can write under it
+ */
+fn check_publish_access(meta_contract_id: &str, public_key: &str, alias: &str) -> Result<(), ServiceError> {
+    // reserved ids are shared by every collection, nobody can suspend them or restrict them
+    if is_native(meta_contract_id) {
+        return Ok(());
+    }
+
+    let storage = get_storage();
+
+    if let Some(suspension) = storage.get_meta_contract_suspension(meta_contract_id.to_string())? {
+        return Err(Suspended(f!("{meta_contract_id} by {suspension.transaction_hash}")));
+    }
+
+    match storage.get_meta_contract_policy(meta_contract_id.to_string())? {
+        Some(policy) if !policy.allows(public_key, alias) => Err(NotAllowed(f!("{public_key} on {meta_contract_id}"))),
+        _ => Ok(()),
+    }
+}
+
+/**
+ * Only the key that first registered a meta contract owns it, binding it elsewhere gives no ownership.
+ * Reserved native ids are not registered, so nobody owns them
+ */
+fn is_meta_contract_owner(meta_contract_id: &str, public_key: &str) -> bool {
+    get_storage()
+        .is_meta_contract_registrant(meta_contract_id.to_string(), public_key)
+        .unwrap_or(false)
+}
+
+/**
+ * A key can only remove a binding it made itself
+ */
+fn check_unbind_request(tx_request: &TransactionRequest, token_key: &str) -> Result<(), ServiceError> {
+    if tx_request.data.is_empty() {
+        return Err(ServiceError::NoProgramId());
+    }
+
+    let binding = get_storage().get_meta_contract_binding(token_key.to_string(), tx_request.data.clone())?;
+
+    if tx_request.public_key != binding.public_key {
+        return Err(InvalidOwner(f!("not owner of meta contract binding: {tx_request.public_key}")));
+    }
+
+    Ok(())
+}
+
+/**
+ * A record freeze needs an existing record owned by the signer, a data_key freeze needs
+ * the signer to own the collection of the data_key. Nothing can be frozen twice
+ */
+fn check_freeze_request(
+    data: &str,
+    public_key: &str,
+    alias: &str,
+    version: &str,
+    data_key: &str,
+    token_key: &str,
+    meta_contract_id: &str,
+) -> Result<(), ServiceError> {
+    let storage = get_storage();
+
+    let serde_freeze: SerdeFreeze = serde_json::from_str(data)
+        .map_err(|e| ServiceError::InvalidDataFormatForMethodType(e.to_string()))?;
+
+    match serde_freeze.scope.as_str() {
+        FREEZE_SCOPE_RECORD => {
+            storage.get_owner_metadata(
+                data_key.to_string(),
+                meta_contract_id.to_string(),
+                public_key.to_string(),
+                alias.to_string(),
+                version.to_string(),
+            )?;
+        }
+        FREEZE_SCOPE_DATA_KEY => {
+            // the key that bound the default meta contract of the token_key owns the collection
+            let token_owner = match storage.get_meta_contract_by_tokenkey(token_key.to_string()) {
+                Ok(meta_contract) => meta_contract.public_key,
+                Err(ServiceError::RecordNotFound(_)) => "".to_string(),
+                Err(e) => return Err(e),
+            };
+
+            if token_owner.is_empty() || token_owner != public_key {
+                return Err(InvalidOwner(f!("not owner of token_key: {public_key}")));
+            }
+        }
+        _ => return Err(ServiceError::InvalidDataFormatForMethodType(serde_freeze.scope)),
+    }
+
+    let freeze = storage.get_metadata_freeze(
+        data_key.to_string(),
+        meta_contract_id.to_string(),
+        public_key.to_string(),
+        alias.to_string(),
+        version.to_string(),
+    )?;
+
+    match freeze {
+        Some(freeze) => Err(Frozen(f!("frozen by {freeze.transaction_hash}"))),
+        None => Ok(()),
+    }
+}
+
+/**
+ * The signer must own what is transferred. When the recipient co-signs, their signature
+ * over the same data must verify against the new public key
+ */
+fn check_transfer_request(
+    tx_request: &TransactionRequest,
+    data_key: &str,
+    token_key: &str,
+) -> Result<(), ServiceError> {
+    let storage = get_storage();
+
+    let serde_transfer: SerdeTransfer = serde_json::from_str(&tx_request.data)
+        .map_err(|e| ServiceError::InvalidDataFormatForMethodType(e.to_string()))?;
+
+    let new_public_key = serde_transfer.new_public_key;
+    let new_enc = get_public_key_type(&new_public_key);
+
+    if new_enc.ne(ENCRYPTION_TYPE_SECP256K1) && new_enc.ne(ENCRYPTION_TYPE_ED25519) {
+        return Err(ServiceError::InvalidEncryption(new_public_key));
+    }
+
+    if new_public_key == tx_request.public_key {
+        return Err(ServiceError::InvalidDataFormatForMethodType(f!("already owned by {new_public_key}")));
+    }
+
+    match serde_transfer.scope.as_str() {
+        TRANSFER_SCOPE_METADATA => {
+            if tx_request.meta_contract_id.is_empty() {
+                return Err(ServiceError::NoProgramId());
+            }
+
+            let metadata = storage.get_owner_metadata(
+                data_key.to_string(),
+                tx_request.meta_contract_id.clone(),
+                tx_request.public_key.clone(),
+                tx_request.alias.clone(),
+                tx_request.version.clone(),
+            )?;
+
+            if !metadata.tombstone.is_empty() {
+                return Err(Deleted(metadata.tombstone));
+            }
+
+            let freeze = storage.get_metadata_freeze(
+                metadata.data_key,
+                metadata.meta_contract_id.clone(),
+                metadata.public_key,
+                metadata.alias.clone(),
+                metadata.version.clone(),
+            )?;
+
+            if let Some(freeze) = freeze {
+                return Err(Frozen(f!("frozen by {freeze.transaction_hash}")));
+            }
+
+            let existing = storage.get_owner_metadata(
+                data_key.to_string(),
+                metadata.meta_contract_id,
+                new_public_key.clone(),
+                metadata.alias,
+                metadata.version,
+            );
+
+            match existing {
+                Ok(_) => return Err(RecordFound(f!("{new_public_key} already owns this record"))),
+                Err(ServiceError::RecordNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        TRANSFER_SCOPE_META_CONTRACT => {
+            if token_key.is_empty() {
+                return Err(ServiceError::InvalidDataFormatForMethodType(serde_transfer.scope));
+            }
+
+            let meta_contract = storage.get_meta_contract_by_tokenkey(token_key.to_string())?;
+
+            if meta_contract.public_key != tx_request.public_key {
+                return Err(InvalidOwner(f!("not owner of default meta contract: {tx_request.public_key}")));
+            }
+        }
+        _ => return Err(ServiceError::InvalidDataFormatForMethodType(serde_transfer.scope)),
+    }
+
+    if !tx_request.mcdata.is_empty() {
+        let consent: SerdeTransferConsent = serde_json::from_str(&tx_request.mcdata)
+            .map_err(|e| ServiceError::InvalidDataFormatForMethodType(e.to_string()))?;
+
+        if !verify(new_public_key.clone(), consent.recipient_signature, tx_request.data.clone(), new_enc) {
+            return Err(InvalidSignature(f!("not signed by recipient: {new_public_key}")));
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * A grant is used by its grantee, while active, for a method and a record it covers
+ */
+fn check_grant_use(tx_request: &TransactionRequest) -> Result<Grant, ServiceError> {
+    let grant = get_storage().get_grant(tx_request.grant_hash.clone())?;
+
+    if grant.grantee != tx_request.public_key {
+        return Err(InvalidGrant(f!("not granted to {tx_request.public_key}")));
+    }
+
+    let now = SystemTime::now();
+    let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
+
+    if !grant.is_active(timestamp.as_millis() as u64) {
+        return Err(InvalidGrant(f!("{grant.hash} is revoked or expired")));
+    }
+
+    if !grant.allows(&tx_request.method) {
+        return Err(InvalidGrant(f!("{tx_request.method} is not granted")));
+    }
+
+    let token_key = Metadata::generate_token_key(tx_request.chain_id.clone(), tx_request.token_address.clone());
+    let data_key = Metadata::generate_data_key(
+        tx_request.chain_id.clone(),
+        tx_request.token_address.clone(),
+        tx_request.token_id.clone(),
+    );
+
+    if !grant.covers(&token_key, &data_key, &tx_request.meta_contract_id, &tx_request.alias) {
+        return Err(InvalidGrant(f!("{grant.hash} does not cover this record")));
+    }
+
+    Ok(grant)
+}
+
+/**
+ * Grants are created and revoked by the grantor only, and only for methods that can be delegated
+ */
+fn check_grant_request(tx_request: &TransactionRequest, token_key: &str) -> Result<(), ServiceError> {
+    let serde_grant: SerdeGrant = serde_json::from_str(&tx_request.data)
+        .map_err(|e| ServiceError::InvalidDataFormatForMethodType(e.to_string()))?;
+
+    match serde_grant.action.as_str() {
+        GRANT_ACTION_CREATE => {
+            let grantee_enc = get_public_key_type(&serde_grant.grantee);
+
+            if grantee_enc.ne(ENCRYPTION_TYPE_SECP256K1) && grantee_enc.ne(ENCRYPTION_TYPE_ED25519) {
+                return Err(ServiceError::InvalidEncryption(serde_grant.grantee));
+            }
+
+            if serde_grant.grantee == tx_request.public_key {
+                return Err(InvalidGrant(f!("cannot grant to self: {serde_grant.grantee}")));
+            }
+
+            if token_key.is_empty() {
+                return Err(InvalidGrant("chain_id and token_address are required".to_string()));
+            }
+
+            if serde_grant.scope != GRANT_SCOPE_TOKEN_KEY
+                && serde_grant.scope != GRANT_SCOPE_DATA_KEY
+                && serde_grant.scope != GRANT_SCOPE_ALIAS
+            {
+                return Err(InvalidGrant(f!("invalid scope: {serde_grant.scope}")));
+            }
+
+            if serde_grant.methods.is_empty() {
+                return Err(InvalidGrant("no methods granted".to_string()));
+            }
+
+            if let Some(method) = serde_grant
+                .methods
+                .iter()
+                .find(|method| !GRANT_DELEGABLE_METHODS.contains(&method.as_str()))
+            {
+                return Err(InvalidGrant(f!("{method} cannot be delegated")));
+            }
+        }
+        GRANT_ACTION_REVOKE => {
+            let grant = get_storage().get_grant(serde_grant.grant_hash)?;
+
+            if grant.grantor != tx_request.public_key {
+                return Err(InvalidOwner(f!("not grantor of {grant.hash}")));
+            }
+
+            if !grant.revoked.is_empty() {
+                return Err(InvalidGrant(f!("{grant.hash} already revoked")));
+            }
+        }
+        _ => return Err(ServiceError::InvalidDataFormatForMethodType(serde_grant.action)),
+    }
+
+    Ok(())
+}
+
 #[marine]
 pub fn publish_batch(
   txs: Vec<TransactionRequest>
@@ -354,6 +1067,7 @@ pub fn send_cron_tx(
     tx_block_number: u64,
     tx_hash: String,
     token_id: String,
+    block_hash: String,
 ) -> FdbCronTxResult {
     let mut error: Option<ServiceError> = None;
     let mut success = true;
@@ -375,7 +1089,7 @@ pub fn send_cron_tx(
 
             match logs {
                 Ok(tx) => {
-                    if tx.status == CRON_TX_STATUS_FAILED {
+                    if tx.status == CRON_TX_STATUS_FAILED || tx.status == CRON_TX_STATUS_REVERTED {
                         proceed = true;
                     } else {
                         cron_tx = tx;
@@ -388,8 +1102,10 @@ pub fn send_cron_tx(
             }
 
             if proceed {
+                let data_key = cron_data.event_data_key(&token_id, &data_key);
                 let now = SystemTime::now();
                 let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
+                let asset = Asset::new(&cron_data.chain, &cron_data.token_type, &cron_data.address, &token_id);
 
                 cron_tx = CronTx::new(
                     cron_data.address,
@@ -407,8 +1123,10 @@ pub fn send_cron_tx(
                     data_key,
                     cron_data.token_key,
                 );
+                cron_tx.block_hash = block_hash;
 
                 let _ = storage.write_cron_tx(cron_tx.clone());
+                let _ = storage.write_asset(asset);
             }
         }
         Err(ServiceError::RecordNotFound(_)) => {}
@@ -432,6 +1150,14 @@ pub fn get_transaction(hash: String) -> FdbTransactionResult {
     wrapped_try(|| get_storage().get_transaction(hash)).into()
 }
 
+/**
+ * Receipts of every meta contract that ran a transaction, the transaction fails when one of them does
+ */
+#[marine]
+pub fn get_transaction_receipts(hash: String) -> FdbTransactionReceiptsResult {
+    wrapped_try(|| get_storage().get_contract_receipts(hash)).into()
+}
+
 #[marine]
 pub fn get_complete_transactions(from: i64, to: i64) -> FdbTransactionsResult {
     let mut ts: i64 = 0;
@@ -466,14 +1192,163 @@ pub fn get_metadata(data_key: String,
   version: String,
 ) -> FdbMetadataResult {
     wrapped_try(|| {
-        get_storage().get_owner_metadata(data_key, meta_contract_id, public_key, alias, version)
+        let storage = get_storage();
+        let data_key = Asset::resolve_data_key(&data_key)?;
+        let metadata = storage.get_owner_metadata(
+            data_key.clone(),
+            meta_contract_id.clone(),
+            public_key.clone(),
+            alias.clone(),
+            version.clone(),
+        )?;
+        let freeze = storage.get_metadata_freeze(data_key, meta_contract_id, public_key, alias, version)?;
+
+        Ok((metadata, freeze))
     })
     .into()
 }
 
 #[marine]
 pub fn get_metadatas(data_key: String, version: String) -> FdbMetadatasResult {
-    wrapped_try(|| get_storage().get_metadata_by_datakey_and_version(data_key, version)).into()
+    wrapped_try(|| {
+        let data_key = Asset::resolve_data_key(&data_key)?;
+        get_storage().get_metadata_by_datakey_and_version(data_key, version)
+    })
+    .into()
+}
+
+/**
+ * CAIP-19 asset a data_key was derived from
+ */
+#[marine]
+pub fn get_asset(data_key: String) -> FdbAssetResult {
+    wrapped_try(|| get_storage().get_asset(data_key)).into()
+}
+
+/**
+ * Collection level records of a token contract, keyed by its token_key
+ */
+#[marine]
+pub fn get_collection_metadatas(token_key: String, version: String) -> FdbMetadatasResult {
+    wrapped_try(|| get_storage().get_metadata_by_datakey_and_version(token_key, version)).into()
+}
+
+/**
+ * Block content of a metadata record. With `inherit_collection`, fields the record leaves out are taken
+ * from the collection record with the same meta contract, owner, alias and version
+ */
+#[marine]
+pub fn get_metadata_content(
+  data_key: String,
+  meta_contract_id: String,
+  public_key: String,
+  alias: String,
+  version: String,
+  inherit_collection: bool,
+) -> FdbMetadataContentResult {
+    wrapped_try(|| {
+        let storage = get_storage();
+        let metadata = storage.get_owner_metadata(
+            data_key,
+            meta_contract_id.clone(),
+            public_key.clone(),
+            alias.clone(),
+            version.clone(),
+        )?;
+
+        let mut content = if metadata.cid.is_empty() {
+            serde_json::json!({})
+        } else {
+            Block::fetch(metadata.cid.clone())?.content
+        };
+        let mut collection_cid = "".to_string();
+
+        if inherit_collection && !metadata.is_collection() {
+            let result = storage.get_owner_metadata(
+                metadata.token_key.clone(),
+                meta_contract_id,
+                public_key,
+                alias,
+                version,
+            );
+
+            match result {
+                Ok(collection) if collection.tombstone.is_empty() && !collection.cid.is_empty() => {
+                    content = inherit(Block::fetch(collection.cid.clone())?.content, content);
+                    collection_cid = collection.cid;
+                }
+                Ok(_) | Err(ServiceError::RecordNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(MetadataContent {
+            metadata,
+            content: serde_json::to_string(&content)?,
+            collection_cid,
+        })
+    })
+    .into()
+}
+
+/**
+ * ERC-721 / ERC-1155 metadata document of a token, as served by a `tokenURI`.
+ * The aliases of the profile, defined by the token's meta contract, are merged so that
+ * higher priority aliases override lower ones. An empty profile selects "default".
+ * Only the records of the collection owner, the key of the token's meta contract, are authoritative
+ */
+#[marine]
+pub fn get_token_metadata(
+  chain_id: String,
+  token_address: String,
+  token_id: String,
+  profile: String,
+) -> FdbTokenMetadataResult {
+    wrapped_try(|| {
+        let storage = get_storage();
+
+        let token_key = Metadata::generate_token_key(chain_id.clone(), token_address.clone());
+        let data_key = Metadata::generate_data_key(chain_id, token_address, token_id);
+
+        let meta_contract = storage.get_meta_contract_by_tokenkey(token_key)?;
+
+        let name = if profile.is_empty() {
+            DEFAULT_METADATA_PROFILE.to_string()
+        } else {
+            profile
+        };
+
+        let profile = storage.get_metadata_profile(meta_contract.meta_contract_id.clone(), name)?;
+
+        let mut merged = serde_json::json!({});
+
+        for alias in profile.alias_list().into_iter().rev() {
+            let result = storage.get_owner_metadata(
+                data_key.clone(),
+                meta_contract.meta_contract_id.clone(),
+                meta_contract.public_key.clone(),
+                alias,
+                "".to_string(),
+            );
+
+            let metadata = match result {
+                Ok(metadata) => Some(metadata),
+                Err(ServiceError::RecordNotFound(_)) => None,
+                Err(e) => return Err(e),
+            };
+
+            if let Some(metadata) = metadata.filter(|metadata| metadata.tombstone.is_empty() && !metadata.cid.is_empty()) {
+                let content = Block::fetch(metadata.cid)?.content;
+
+                if content.is_object() {
+                    merged = inherit(merged, content);
+                }
+            }
+        }
+
+        Ok(serde_json::to_string(&token_metadata::render(merged))?)
+    })
+    .into()
 }
 
 #[marine]
@@ -486,6 +1361,38 @@ pub fn search_metadatas(
     wrapped_try(|| get_storage().search_metadatas(query, ordering, from, to)).into()
 }
 
+/**
+ * Full-text search over metadata content, ranked by relevance with highlighted snippets.
+ * Pass the returned `next_cursor` to read the next page, it is empty on the last one
+ */
+#[marine]
+pub fn search_metadata_text(
+    query: String,
+    filters: Vec<MetadataQuery>,
+    cursor: String,
+) -> FdbMetadataTextSearchResult {
+    wrapped_try(|| {
+        let offset = if cursor.is_empty() {
+            0
+        } else {
+            cursor
+                .parse::<u32>()
+                .map_err(|_| ServiceError::InternalError(format!("Invalid cursor: {}", cursor)))?
+        };
+
+        let results = get_storage().search_metadata_text(query, filters, offset)?;
+
+        let next_cursor = if results.len() as u32 == TEXT_SEARCH_PAGE_SIZE {
+            (offset + TEXT_SEARCH_PAGE_SIZE).to_string()
+        } else {
+            "".to_string()
+        };
+
+        Ok((results, next_cursor))
+    })
+    .into()
+}
+
 #[marine]
 pub fn get_meta_contract(token_key: String) -> FdbMetaContractResult {
     wrapped_try(|| get_storage().get_meta_contract_by_tokenkey(token_key)).into()
@@ -556,6 +1463,104 @@ pub fn get_cron_tx_latest_block(address: String, chain: String, topic: String) -
     .into()
 }
 
+/**
+ * Block range the poller scans next for a cron, given the chain head
+ */
+#[marine]
+pub fn get_cron_range(hash: String, latest_block: u64) -> FdbCronRangeResult {
+    wrapped_try(|| {
+        let storage = get_storage();
+        let mut cron = storage.get_cron_by_hash(hash)?;
+
+        // crons created before the cursor existed resume after their last recorded event,
+        // a cron created without a start block starts at the chain head it is first polled at
+        if cron.last_processed_block == 0 && cron.start_block == 0 {
+            match storage.get_cron_tx_latest_block(cron.address.clone(), cron.chain.clone(), cron.topic.clone()) {
+                Ok(log) => cron.start_block = log.tx_block_number,
+                Err(ServiceError::RecordNotFound(_)) => {
+                    cron.start_block = latest_block.saturating_sub(cron.confirmations);
+                    storage.set_cron_start_block(cron.hash.clone(), cron.start_block)?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(cron.next_range(latest_block))
+    })
+    .into()
+}
+
+/**
+ * Events of a cron recent enough to be taken back by a reorg, to check against the canonical chain
+ */
+#[marine]
+pub fn get_cron_txs_to_reconcile(hash: String, latest_block: u64) -> FdbCronTxsResult {
+    wrapped_try(|| {
+        let storage = get_storage();
+        let cron = storage.get_cron_by_hash(hash)?;
+        let window = max(cron.confirmations, CRON_REORG_WINDOW);
+
+        storage.get_unsettled_cron_txs(cron.address, cron.chain, cron.topic, latest_block.saturating_sub(window))
+    })
+    .into()
+}
+
+/**
+ * Compares an indexed event with the block its transaction has on the canonical chain.
+ * An empty hash means the transaction is gone: the event is reverted and the metadata it minted
+ * is tombstoned. A different hash means it was mined again in another block
+ */
+#[marine]
+pub fn reconcile_cron_tx(hash: String, block_hash: String, block_number: u64) -> FdbCronTxResult {
+    wrapped_try(|| {
+        let storage = get_storage();
+        let mut cron_tx = storage.get_cron_tx(hash)?;
+
+        if cron_tx.status != CRON_TX_STATUS_SUCCESS {
+            return Ok(cron_tx);
+        }
+
+        if block_hash.is_empty() {
+            let error_text = f!("orphaned by reorg at block {cron_tx.tx_block_number}");
+            storage.revert_cron_tx(cron_tx.hash.clone(), error_text.clone())?;
+
+            let now = SystemTime::now();
+            let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
+
+            // only the records this event minted go, the ones other transactions wrote stay
+            for metadata in storage.get_live_metadatas_by_transaction(cron_tx.hash.clone())? {
+                storage.tombstone_metadata(metadata.hash.clone(), cron_tx.hash.clone())?;
+                storage.write_metadata_version(MetadataVersion::new(
+                    metadata.hash,
+                    "".to_string(),
+                    metadata.cid,
+                    cron_tx.hash.clone(),
+                    CRON_TX_REVERT.to_string(),
+                    timestamp.as_millis() as u64,
+                ))?;
+            }
+
+            cron_tx.status = CRON_TX_STATUS_REVERTED;
+            cron_tx.error_text = error_text;
+        } else if cron_tx.block_hash != block_hash || cron_tx.tx_block_number != block_number {
+            storage.update_cron_tx_block(cron_tx.hash.clone(), block_hash.clone(), block_number)?;
+            cron_tx.block_hash = block_hash;
+            cron_tx.tx_block_number = block_number;
+        }
+
+        Ok(cron_tx)
+    })
+    .into()
+}
+
+/**
+ * Called by the poller once every block of a range is processed
+ */
+#[marine]
+pub fn advance_cron_cursor(hash: String, block: u64) -> FdbCronResult {
+    wrapped_try(|| get_storage().advance_cron_cursor(hash, block)).into()
+}
+
 #[marine]
 pub fn get_metadata_with_history(
   data_key: String,
@@ -579,28 +1584,251 @@ pub fn get_metadata_with_history(
             Err(e) => return Err(e),
         };
 
-        let mut read_metadata_cid: String = metadata.cid.clone();
+        let mut read_metadata_cid = Some(metadata.cid.clone());
 
-        while read_metadata_cid.len() > 0 {
-            let result = get(read_metadata_cid.clone(), "".to_string(), 0);
-            let val: Value = serde_json::from_str(&result.block).unwrap();
+        while let Some(cid) = read_metadata_cid.filter(|cid| !cid.is_empty()) {
+            let block = Block::fetch(cid)?;
 
-            let input = format!(r#"{}"#, val);
-            metadatas.push(input);
+            metadatas.push(serde_json::to_string(&block)?);
 
-            let previous_cid = val
-                .get("previous")
-                .and_then(|v| v.get("/"))
-                .and_then(|v| v.as_str());
+            read_metadata_cid = block.previous_cid();
+        }
 
-            if previous_cid.is_none() {
-                break;
-            } else {
-                read_metadata_cid = previous_cid.unwrap().to_string();
+        Ok(metadatas)
+    })
+    .into()
+}
+
+/**
+ * Meta contracts of a token_key in the order they run
+ */
+#[marine]
+pub fn get_bound_meta_contracts(token_key: String) -> FdbMetaContractsResult {
+    wrapped_try(|| get_storage().get_bound_meta_contracts(token_key)).into()
+}
+
+/**
+ * Publishing policy of a meta contract, `restricted` is false when anyone can publish
+ */
+#[marine]
+pub fn get_meta_contract_policy(meta_contract_id: String) -> FdbMetaContractPolicyResult {
+    wrapped_try(|| get_storage().get_meta_contract_policy(meta_contract_id)).into()
+}
+
+/**
+ * Records a metadata was cloned from, back to the original creation
+ */
+#[marine]
+pub fn get_clone_ancestors(metadata_hash: String) -> FdbCloneEdgesResult {
+    wrapped_try(|| get_storage().get_clone_ancestors(metadata_hash)).into()
+}
+
+/**
+ * Clones made from a metadata, `depth` levels down. 0 walks the whole tree
+ */
+#[marine]
+pub fn get_clone_descendants(metadata_hash: String, depth: u32) -> FdbCloneEdgesResult {
+    let depth = if depth == 0 || depth > CLONE_LINEAGE_MAX_DEPTH {
+        CLONE_LINEAGE_MAX_DEPTH
+    } else {
+        depth
+    };
+
+    wrapped_try(|| get_storage().get_clone_descendants(metadata_hash, depth)).into()
+}
+
+/**
+ * Suspension of a meta contract, `suspended` is false when it runs normally
+ */
+#[marine]
+pub fn get_meta_contract_suspension(meta_contract_id: String) -> FdbMetaContractSuspensionResult {
+    wrapped_try(|| get_storage().get_meta_contract_suspension(meta_contract_id)).into()
+}
+
+/**
+ * Token keys a meta contract is bound to
+ */
+#[marine]
+pub fn get_meta_contract_bindings(meta_contract_id: String) -> FdbMetaContractBindingsResult {
+    wrapped_try(|| get_storage().get_meta_contract_bindings(meta_contract_id)).into()
+}
+
+/**
+ * Meta contracts bound to a token_key over time, newest first
+ */
+#[marine]
+pub fn get_meta_contract_history(token_key: String) -> FdbMetaContractVersionsResult {
+    wrapped_try(|| get_storage().get_meta_contract_versions(token_key)).into()
+}
+
+/**
+ * Grants given by or to a public key, newest first
+ */
+#[marine]
+pub fn get_grants(public_key: String) -> FdbGrantsResult {
+    wrapped_try(|| get_storage().get_grants(public_key)).into()
+}
+
+/**
+ * Owners of a metadata record (by its hash) or of a token_key's meta contract binding, oldest first
+ */
+#[marine]
+pub fn get_ownership_history(subject: String) -> FdbOwnershipTransfersResult {
+    wrapped_try(|| get_storage().get_ownership_transfers(subject)).into()
+}
+
+/**
+ * Version history of a metadata record, read from the versions table.
+ * Block content is only fetched from IPFS when `with_content` is set
+ */
+#[marine]
+pub fn get_metadata_history(
+  metadata_hash: String,
+  sort: String,
+  offset: u32,
+  limit: u32,
+  with_content: bool,
+) -> FdbMetadataVersionsResult {
+    wrapped_try(|| {
+        let mut versions = get_storage().get_metadata_versions(metadata_hash, sort, offset, limit)?;
+
+        if with_content {
+            // deletions have no block to read
+            for version in versions.iter_mut().filter(|version| !version.cid.is_empty()) {
+                let block = Block::fetch(version.cid.clone())?;
+                version.content = block.content.to_string();
             }
         }
 
-        Ok(metadatas)
+        Ok(versions)
+    })
+    .into()
+}
+
+/**
+ * Content of a metadata record as it was at a point in time.
+ * `timestamp_or_tx_hash` is either a millisecond timestamp or a transaction hash,
+ * in which case the timestamp of that transaction is used
+ */
+#[marine]
+pub fn get_metadata_at(
+  data_key: String,
+  meta_contract_id: String,
+  public_key: String,
+  alias: String,
+  version: String,
+  timestamp_or_tx_hash: String,
+) -> FdbMetadataVersionResult {
+    wrapped_try(|| {
+        let storage = get_storage();
+
+        let metadata = storage.get_owner_metadata(data_key, meta_contract_id, public_key, alias, version)?;
+
+        let at = match timestamp_or_tx_hash.parse::<u64>() {
+            Ok(timestamp) => timestamp,
+            Err(_) => storage.get_transaction(timestamp_or_tx_hash)?.timestamp,
+        };
+
+        let oldest = storage.get_metadata_versions(metadata.hash.clone(), "asc".to_string(), 0, 1)?;
+
+        let mut version = match oldest.first() {
+            // written before versions were recorded, walk the block chain instead
+            None => find_version_in_chain(metadata.hash, metadata.cid, at)?,
+            // older than the first recorded version, the chain goes on from the block it was written on top of
+            Some(oldest) if oldest.timestamp > at => {
+                find_version_in_chain(metadata.hash, oldest.previous_cid.clone(), at)?
+            }
+            Some(_) => storage.get_metadata_version_at(metadata.hash, at)?,
+        };
+
+        if version.content.is_empty() && !version.cid.is_empty() {
+            version.content = Block::fetch(version.cid.clone())?.content.to_string();
+        }
+
+        Ok(version)
+    })
+    .into()
+}
+
+/**
+ * Follows the `previous` links of a metadata record from `cid` until it reaches the block
+ * whose transaction happened at or before `at`
+ */
+fn find_version_in_chain(metadata_hash: String, cid: String, at: u64) -> Result<MetadataVersion, ServiceError> {
+    let storage = get_storage();
+    let mut read_metadata_cid = Some(cid);
+
+    while let Some(cid) = read_metadata_cid.filter(|cid| !cid.is_empty()) {
+        let block = Block::fetch(cid.clone())?;
+
+        let transaction_hash = block
+            .transaction
+            .get("hash")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        let (method, timestamp) = match storage.get_transaction(transaction_hash.clone()) {
+            Ok(transaction) => (transaction.method, transaction.timestamp),
+            Err(_) => ("".to_string(), block.timestamp),
+        };
+
+        if timestamp <= at {
+            return Ok(MetadataVersion {
+                metadata_hash,
+                cid,
+                previous_cid: block.previous_cid().unwrap_or_default(),
+                transaction_hash,
+                method,
+                timestamp,
+                content: block.content.to_string(),
+            });
+        }
+
+        read_metadata_cid = block.previous_cid();
+    }
+
+    Err(ServiceError::RecordNotFound(format!("No version at {}", at)))
+}
+
+/**
+ * Structural diff between the contents of two metadata blocks.
+ * When `cid_a` is empty, `cid_b` is compared with the version it was written on top of
+ */
+#[marine]
+pub fn diff_metadata(cid_a: String, cid_b: String) -> FdbMetadataDiffResult {
+    wrapped_try(|| {
+        let block_b = Block::fetch(cid_b.clone())?;
+
+        let cid_a = if cid_a.is_empty() {
+            block_b
+                .previous_cid()
+                .ok_or_else(|| ServiceError::RecordNotFound(format!("No previous version: {}", cid_b)))?
+        } else {
+            cid_a
+        };
+
+        let block_a = Block::fetch(cid_a)?;
+
+        MetadataDiff::between(&block_a.content, &block_b.content)
+    })
+    .into()
+}
+
+/**
+ * Re-keys records written before chain ids, addresses and token ids were normalized,
+ * merging the ones that now share a key. `init` runs it, only the service creator can run it again
+ */
+#[marine]
+pub fn migrate_normalized_keys() -> FdbMigrationResult {
+    wrapped_try(|| {
+        let cp = marine_rs_sdk::get_call_parameters();
+
+        if cp.init_peer_id != cp.service_creator_peer_id {
+            return Err(InvalidOwner(f!("not service creator: {cp.init_peer_id}")));
+        }
+
+        get_storage().migrate_normalized_keys()
     })
     .into()
 }
@@ -628,38 +1856,124 @@ pub fn set_metadata(
     );
 }
 
+/**
+ * Writes the metadata minted by an indexed event. Its versions point at `cron_tx_hash` so a reorg can take them back
+ */
 #[marine]
 pub fn set_metadata_cron(
+    cron_tx_hash: String,
     meta_contract: MetaContract,
     data_key: String,
     on_metacontract_result: bool,
     metadatas: Vec<FinalMetadata>,
 ) {
-    validate_metadata_cron(meta_contract, data_key, on_metacontract_result, metadatas);
+    validate_metadata_cron(cron_tx_hash, meta_contract, data_key, on_metacontract_result, metadatas);
 }
 
 #[marine]
 pub fn set_clone(
     transaction_hash: String,
     meta_contract_id: String,
-    on_metacontract_result: bool,
-    data: String,
-    final_error_msg: String,
+    policy: ForkPolicyResult,
 ) {
     validate_clone(
         transaction_hash,
         meta_contract_id,
-        on_metacontract_result,
-        data,
-        final_error_msg,
+        policy,
     );
 }
 
+#[marine]
+pub fn set_clone_failed(transaction_hash: String, error_text: String) {
+    validate_clone_failed(transaction_hash, error_text);
+}
+
+#[marine]
+pub fn set_clone_native(transaction_hash: String, meta_contract_id: String) {
+    validate_clone_native(transaction_hash, meta_contract_id);
+}
+
+/**
+ * Record a clone transaction copies, with its freeze state. Its meta contract decides the fork
+ */
+#[marine]
+pub fn get_clone_origin(transaction_hash: String) -> FdbMetadataResult {
+    wrapped_try(|| {
+        let storage = get_storage();
+        let transaction = storage.get_transaction(transaction_hash)?;
+        let data_clone: DataTypeClone = serde_json::from_str(&transaction.data)?;
+
+        let metadata = storage.get_owner_metadata(
+            data_clone.origin_data_key.clone(),
+            data_clone.origin_meta_contract_id.clone(),
+            data_clone.origin_public_key.clone(),
+            data_clone.origin_alias.clone(),
+            data_clone.origin_version.clone(),
+        )?;
+        let freeze = storage.get_metadata_freeze(
+            data_clone.origin_data_key,
+            data_clone.origin_meta_contract_id,
+            data_clone.origin_public_key,
+            data_clone.origin_alias,
+            data_clone.origin_version,
+        )?;
+
+        Ok((metadata, freeze))
+    })
+    .into()
+}
+
 #[marine]
 pub fn set_cron(transaction_hash: String) {
     validate_cron(transaction_hash);
 }
 
+#[marine]
+pub fn set_freeze(transaction_hash: String) {
+    validate_freeze(transaction_hash);
+}
+
+#[marine]
+pub fn set_delete(transaction_hash: String) {
+    validate_delete(transaction_hash);
+}
+
+#[marine]
+pub fn set_transfer(transaction_hash: String) {
+    validate_transfer(transaction_hash);
+}
+
+#[marine]
+pub fn set_grant(transaction_hash: String) {
+    validate_grant(transaction_hash);
+}
+
+/**
+ * Runs a built-in policy for a metadata transaction in place of a meta contract service
+ */
+#[marine]
+pub fn set_metadata_native(transaction_hash: String, meta_contract_id: String) {
+    validate_metadata_native(transaction_hash, meta_contract_id);
+}
+
+/**
+ * Meta contract ids starting with "native:" name built-in policies, not services
+ */
+#[marine]
+pub fn is_native_meta_contract(meta_contract_id: String) -> bool {
+    is_native(&meta_contract_id)
+}
+
+#[marine]
+pub fn set_unbind(transaction_hash: String) {
+    validate_unbind(transaction_hash);
+}
+
+#[marine]
+pub fn set_suspend(transaction_hash: String) {
+    validate_suspend(transaction_hash);
+}
+
 // *********** Deserializer *****************
 #[marine]
 pub fn deserialize_fork(data: String) -> DataTypeFork {
@@ -683,6 +1997,9 @@ extern "C" {
 
     #[link_name = "get"]
     pub fn get(hash: String, api_multiaddr: String, timeout_sec: u64) -> IpfsDagGetResult;
+
+    #[link_name = "unpin"]
+    pub fn unpin(hash: String, api_multiaddr: String, timeout_sec: u64) -> IpfsUnpinResult;
 }
 
 #[marine]
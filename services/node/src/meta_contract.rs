@@ -1,6 +1,10 @@
+use std::collections::HashMap;
+
 use marine_rs_sdk::marine;
 use serde::Deserialize;
 
+use crate::policy::SerdePolicy;
+
 #[marine]
 #[derive(Debug, Default, Clone, Deserialize)]
 pub struct MetaContract {
@@ -9,3 +13,17 @@ pub struct MetaContract {
     pub public_key: String,
     pub cid: String,
 }
+
+/**
+ * Configuration carried in the `mcdata` of a contract transaction
+ */
+#[derive(Debug, Default, Deserialize)]
+pub struct SerdeMetaContract {
+    pub indexed_paths: Option<Vec<String>>,
+    /** Rendering profiles for `get_token_metadata`, aliases listed from highest priority */
+    pub profiles: Option<HashMap<String, Vec<String>>>,
+    /** CID of the contract code, recorded with the binding */
+    pub cid: Option<String>,
+    /** Who can publish metadata under the contract, only its owner can change it */
+    pub policy: Option<SerdePolicy>,
+}
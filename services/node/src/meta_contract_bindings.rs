@@ -0,0 +1,17 @@
+use marine_rs_sdk::marine;
+use serde::Deserialize;
+
+/**
+ * Meta contract serving a token_key. A token_key runs its contracts by ascending `position`
+ */
+#[marine]
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MetaContractBinding {
+    pub token_key: String,
+    pub meta_contract_id: String,
+    pub public_key: String,
+    pub cid: String,
+    pub position: i64,
+    pub transaction_hash: String,
+    pub timestamp: u64,
+}
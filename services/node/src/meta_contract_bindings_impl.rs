@@ -0,0 +1,148 @@
+use crate::defaults::{META_CONTRACT_BINDINGS_TABLE_NAME, META_CONTRACT_TABLE_NAME};
+use crate::error::ServiceError;
+use crate::meta_contract::MetaContract;
+use crate::meta_contract_bindings::MetaContractBinding;
+use crate::meta_contract_impl;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+
+impl Storage {
+    pub fn create_meta_contract_bindings_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                token_key TEXT not null,
+                meta_contract_id TEXT not null,
+                public_key TEXT not null,
+                cid TEXT not null,
+                position INTEGER not null,
+                transaction_hash TEXT not null,
+                timestamp INTEGER not null,
+                PRIMARY KEY (token_key, meta_contract_id)
+            )",
+            META_CONTRACT_BINDINGS_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_meta_contract_bindings_table error: {}", error);
+        }
+
+        // the default meta contract of a token_key deployed before bindings existed stays bound, first
+        let backfill = format!(
+            "insert or ignore into {} (token_key, meta_contract_id, public_key, cid, position, transaction_hash, timestamp)
+            SELECT token_key, meta_contract_id, COALESCE(public_key, ''), COALESCE(cid, ''), 0, '', 0 FROM {} WHERE COALESCE(meta_contract_id, '') != ''",
+            META_CONTRACT_BINDINGS_TABLE_NAME, META_CONTRACT_TABLE_NAME
+        );
+
+        if let Err(error) = Storage::execute(backfill) {
+            println!("create_meta_contract_bindings_table backfill error: {}", error);
+        }
+    }
+
+    /**
+     * Appends a meta contract to the contracts of its token_key.
+     * Binding it again keeps its position and updates its cid
+     */
+    pub fn bind_meta_contract(
+        &self,
+        contract: &MetaContract,
+        transaction_hash: String,
+        timestamp: u64,
+    ) -> Result<(), ServiceError> {
+        let statement = format!(
+            "insert into {table} (token_key, meta_contract_id, public_key, cid, position, transaction_hash, timestamp)
+            SELECT '{}', '{}', '{}', '{}', COALESCE(MAX(position), 0) + 1, '{}', {} FROM {table} WHERE token_key = '{}'
+            ON CONFLICT(token_key, meta_contract_id) DO UPDATE SET cid = excluded.cid, transaction_hash = excluded.transaction_hash, timestamp = excluded.timestamp",
+            contract.token_key,
+            contract.meta_contract_id,
+            contract.public_key,
+            contract.cid,
+            transaction_hash,
+            timestamp,
+            contract.token_key,
+            table = META_CONTRACT_BINDINGS_TABLE_NAME,
+        );
+
+        Storage::execute(statement)?;
+
+        Ok(())
+    }
+
+    pub fn unbind_meta_contract(&self, token_key: String, meta_contract_id: String) -> Result<(), ServiceError> {
+        let statement = format!(
+            "DELETE FROM {} WHERE token_key = '{}' AND meta_contract_id = '{}'",
+            META_CONTRACT_BINDINGS_TABLE_NAME, token_key, meta_contract_id
+        );
+
+        Storage::execute(statement)?;
+
+        Ok(())
+    }
+
+    pub fn get_meta_contract_binding(
+        &self,
+        token_key: String,
+        meta_contract_id: String,
+    ) -> Result<MetaContractBinding, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE token_key = '{}' AND meta_contract_id = '{}'",
+            META_CONTRACT_BINDINGS_TABLE_NAME, token_key, meta_contract_id
+        );
+
+        let result = Storage::read(statement)?;
+        match read(result) {
+            Ok(bindings) => bindings
+                .first()
+                .cloned()
+                .ok_or(ServiceError::RecordNotFound(meta_contract_id)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /**
+     * Token keys a meta contract is bound to
+     */
+    pub fn get_meta_contract_bindings(&self, meta_contract_id: String) -> Result<Vec<MetaContractBinding>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE meta_contract_id = '{}' ORDER BY timestamp",
+            META_CONTRACT_BINDINGS_TABLE_NAME, meta_contract_id
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+
+    /**
+     * Meta contracts of a token_key in the order they run
+     */
+    pub fn get_bound_meta_contracts(&self, token_key: String) -> Result<Vec<MetaContract>, ServiceError> {
+        let statement = format!(
+            "SELECT token_key, meta_contract_id, public_key, cid FROM {} WHERE token_key = '{}' ORDER BY position",
+            META_CONTRACT_BINDINGS_TABLE_NAME, token_key
+        );
+
+        let result = Storage::read(statement)?;
+        meta_contract_impl::read(result)
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<MetaContractBinding>, ServiceError> {
+    let mut bindings = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::MetaContractBinding(binding) => bindings.push(binding),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        META_CONTRACT_BINDINGS_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(bindings)
+}
@@ -1,6 +1,7 @@
 use crate::error::ServiceError;
 use crate::error::ServiceError::RecordNotFound;
 use crate::storage_impl::{Storage, RQLiteResult, Row};
+use crate::defaults::META_CONTRACT_BINDINGS_TABLE_NAME;
 use crate::{defaults::META_CONTRACT_TABLE_NAME, meta_contract::MetaContract};
 use marine_sqlite_connector::{State, Statement, Value};
 
@@ -43,22 +44,27 @@ impl Storage {
         Ok(())
     }
 
-    pub fn rebind_meta_contract(
-        &self,
-        token_key: String,
-        meta_contract_id: String,
-        pk: String,
-    ) -> Result<(), ServiceError> {
+    /**
+     * Makes a bound contract the default one of its token_key. The owner of the token_key stays
+     */
+    pub fn set_default_meta_contract(&self, contract: &MetaContract) -> Result<(), ServiceError> {
         let statement = format!(
           "
           update {}
-          set token_key = '{}'
-          where meta_contract_id = '{}'
-          and public_key = '{}'
+          set meta_contract_id = '{}',
+          cid = '{}'
+          where token_key = '{}'
           ",
-            META_CONTRACT_TABLE_NAME, token_key, meta_contract_id, pk,
+            META_CONTRACT_TABLE_NAME, contract.meta_contract_id, contract.cid, contract.token_key,
         );
-        let result = Storage::execute(statement)?;
+        Storage::execute(statement)?;
+
+        Ok(())
+    }
+
+    pub fn delete_meta_contract(&self, token_key: String) -> Result<(), ServiceError> {
+        let statement = format!("DELETE FROM {} WHERE token_key = '{}'", META_CONTRACT_TABLE_NAME, token_key);
+        Storage::execute(statement)?;
 
         Ok(())
     }
@@ -78,9 +84,13 @@ impl Storage {
         }
     }
 
+    /**
+     * A contract that only runs next to the default one of a token_key is found through its bindings
+     */
     pub fn get_meta_contract_by_id(&self, meta_contract_id: String) -> Result<MetaContract, ServiceError> {
-        let statement = format!("SELECT * FROM {} WHERE meta_contract_id = '{}'",
-          META_CONTRACT_TABLE_NAME, meta_contract_id,
+        let statement = format!("SELECT token_key, meta_contract_id, public_key, cid FROM {} WHERE meta_contract_id = '{}'
+          UNION ALL SELECT token_key, meta_contract_id, public_key, cid FROM {} WHERE meta_contract_id = '{}'",
+          META_CONTRACT_TABLE_NAME, meta_contract_id, META_CONTRACT_BINDINGS_TABLE_NAME, meta_contract_id,
         );
 
         let result = Storage::read(statement)?;
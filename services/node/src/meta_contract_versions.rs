@@ -0,0 +1,18 @@
+use marine_rs_sdk::marine;
+use serde::Deserialize;
+
+/**
+ * Binding of a meta contract to a token_key. Every binding change adds a version,
+ * the highest one is active
+ */
+#[marine]
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MetaContractVersion {
+    pub token_key: String,
+    pub version: i64,
+    pub meta_contract_id: String,
+    pub public_key: String,
+    pub cid: String,
+    pub transaction_hash: String,
+    pub timestamp: u64,
+}
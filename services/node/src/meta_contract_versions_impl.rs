@@ -0,0 +1,104 @@
+use crate::defaults::{META_CONTRACT_TABLE_NAME, META_CONTRACT_VERSIONS_TABLE_NAME};
+use crate::error::ServiceError;
+use crate::meta_contract::MetaContract;
+use crate::meta_contract_versions::MetaContractVersion;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+
+impl Storage {
+    pub fn create_meta_contract_versions_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                token_key TEXT not null,
+                version INTEGER not null,
+                meta_contract_id TEXT not null,
+                public_key TEXT not null,
+                cid TEXT not null,
+                transaction_hash TEXT not null,
+                timestamp INTEGER not null,
+                PRIMARY KEY (token_key, version)
+            )",
+            META_CONTRACT_VERSIONS_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_meta_contract_versions_table error: {}", error);
+        }
+
+        // a meta contract deployed before versions were kept is version 1 of its token_key
+        let backfill = format!(
+            "insert or ignore into {versions} (token_key, version, meta_contract_id, public_key, cid, transaction_hash, timestamp)
+            SELECT m.token_key, 1, m.meta_contract_id, COALESCE(m.public_key, ''), COALESCE(m.cid, ''), '', 0 FROM {contracts} m
+            WHERE COALESCE(m.meta_contract_id, '') != '' AND NOT EXISTS (SELECT 1 FROM {versions} v WHERE v.token_key = m.token_key)",
+            versions = META_CONTRACT_VERSIONS_TABLE_NAME,
+            contracts = META_CONTRACT_TABLE_NAME
+        );
+
+        if let Err(error) = Storage::execute(backfill) {
+            println!("create_meta_contract_versions_table backfill error: {}", error);
+        }
+    }
+
+    /**
+     * Records a binding as the next version of its token_key
+     */
+    pub fn write_meta_contract_version(
+        &self,
+        contract: &MetaContract,
+        transaction_hash: String,
+        timestamp: u64,
+    ) -> Result<(), ServiceError> {
+        let statement = format!(
+            "insert into {table} (token_key, version, meta_contract_id, public_key, cid, transaction_hash, timestamp)
+            SELECT '{}', COALESCE(MAX(version), 0) + 1, '{}', '{}', '{}', '{}', {} FROM {table} WHERE token_key = '{}'",
+            contract.token_key,
+            contract.meta_contract_id,
+            contract.public_key,
+            contract.cid,
+            transaction_hash,
+            timestamp,
+            contract.token_key,
+            table = META_CONTRACT_VERSIONS_TABLE_NAME,
+        );
+
+        Storage::execute(statement)?;
+
+        Ok(())
+    }
+
+    /**
+     * Bindings of a token_key, newest first
+     */
+    pub fn get_meta_contract_versions(&self, token_key: String) -> Result<Vec<MetaContractVersion>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE token_key = '{}' ORDER BY version DESC",
+            META_CONTRACT_VERSIONS_TABLE_NAME, token_key
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<MetaContractVersion>, ServiceError> {
+    let mut versions = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::MetaContractVersion(version) => versions.push(version),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        META_CONTRACT_VERSIONS_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(versions)
+}
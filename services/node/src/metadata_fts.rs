@@ -0,0 +1,69 @@
+use marine_rs_sdk::marine;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::metadatas::Metadata;
+
+#[marine]
+#[derive(Debug, Default, Clone)]
+pub struct MetadataTextMatch {
+    pub metadata: Metadata,
+    pub rank: f64,
+    pub snippet: String,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MetadataTextHit {
+    pub metadata_hash: String,
+    pub score: f64,
+    pub snippet: String,
+}
+
+/**
+ * Every text field of a content document, one per line. Arrays keep their order, object fields come by key
+ */
+pub fn content_text(content: &Value) -> String {
+    let mut lines = Vec::new();
+    collect_text(content, &mut lines);
+    lines.join("\n")
+}
+
+fn collect_text(value: &Value, lines: &mut Vec<String>) {
+    match value {
+        Value::String(s) => lines.push(s.clone()),
+        Value::Array(items) => items.iter().for_each(|item| collect_text(item, lines)),
+        Value::Object(fields) => fields.values().for_each(|field| collect_text(field, lines)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn flattens_nested_text_by_key() {
+        let content = json!({
+            "name": "Sword",
+            "attributes": [{"trait": "element", "value": "fire"}],
+            "details": {"origin": "forge"}
+        });
+
+        assert_eq!(content_text(&content), "element\nfire\nforge\nSword");
+    }
+
+    #[test]
+    fn skips_values_that_are_not_text() {
+        let content = json!({"level": 3, "rare": true, "none": null, "tags": [1, "blade"]});
+
+        assert_eq!(content_text(&content), "blade");
+    }
+
+    #[test]
+    fn plain_strings_and_empty_documents() {
+        assert_eq!(content_text(&json!("just text")), "just text");
+        assert_eq!(content_text(&json!({})), "");
+        assert_eq!(content_text(&json!([])), "");
+    }
+}
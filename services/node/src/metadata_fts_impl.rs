@@ -0,0 +1,141 @@
+use serde_json::Value;
+
+use crate::block::Block;
+use crate::defaults::{METADATAS_TABLE_NAME, METADATA_FTS_TABLE_NAME, TEXT_SEARCH_PAGE_SIZE};
+use crate::error::ServiceError;
+use crate::metadata_fts::{content_text, MetadataTextHit, MetadataTextMatch};
+use crate::metadatas::MetadataQuery;
+use crate::metadatas_impl;
+use crate::metadatas_impl::metadata_conditions;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+
+impl Storage {
+    pub fn create_metadata_fts_table(&self) {
+        let table_schema = format!(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS {} USING fts5(metadata_hash UNINDEXED, content)",
+            METADATA_FTS_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_metadata_fts_table error: {}", error);
+        }
+    }
+
+    /**
+     * Replaces the full-text entry of a metadata record with the text fields of its content.
+     * A content without text keeps an empty entry, so the record is known to be indexed
+     */
+    pub fn index_metadata_text(&self, metadata_hash: String, content: &str) -> Result<(), ServiceError> {
+        let document: Value = serde_json::from_str(content).unwrap_or(Value::String(content.to_string()));
+
+        Storage::execute(format!(
+            "DELETE FROM {} WHERE metadata_hash = '{}'",
+            METADATA_FTS_TABLE_NAME, metadata_hash
+        ))?;
+
+        let text = content_text(&document);
+
+        Storage::execute(format!(
+            "insert into {} (metadata_hash, content) values ('{}', '{}')",
+            METADATA_FTS_TABLE_NAME,
+            metadata_hash,
+            Storage::escape(&text),
+        ))?;
+
+        Ok(())
+    }
+
+    /**
+     * Indexes the text of live records written before the full-text table existed.
+     * Their content is read from IPFS, a record that cannot be read is retried on the next init
+     */
+    pub fn backfill_metadata_text(&self) -> Result<(), ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {metadatas} WHERE tombstone = '' AND cid != '' AND hash NOT IN (SELECT metadata_hash FROM {fts})",
+            metadatas = METADATAS_TABLE_NAME,
+            fts = METADATA_FTS_TABLE_NAME
+        );
+
+        let result = Storage::read(statement)?;
+
+        for metadata in metadatas_impl::read(result)? {
+            let content = match Block::fetch(metadata.cid.clone()) {
+                Ok(block) => block.content.to_string(),
+                Err(e) => {
+                    log::info!("{}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = self.index_metadata_text(metadata.hash.clone(), &content) {
+                log::info!("{}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Ranked full-text search over metadata content, best matches first.
+     * `filters` narrow the results on metadata columns, `cursor` is the offset returned by the previous page
+     */
+    pub fn search_metadata_text(
+        &self,
+        query: String,
+        filters: Vec<MetadataQuery>,
+        cursor: u32,
+    ) -> Result<Vec<MetadataTextMatch>, ServiceError> {
+        let mut conditions = vec![format!("{} MATCH '{}'", METADATA_FTS_TABLE_NAME, Storage::escape(&query))];
+        conditions.extend(metadata_conditions(filters));
+
+        let statement = format!(
+            "SELECT {fts}.metadata_hash AS metadata_hash, bm25({fts}) AS score, snippet({fts}, 1, '<b>', '</b>', '...', 16) AS snippet FROM {fts} JOIN {metadatas} ON {metadatas}.hash = {fts}.metadata_hash WHERE {conditions} ORDER BY score LIMIT {cursor},{size}",
+            fts = METADATA_FTS_TABLE_NAME,
+            metadatas = METADATAS_TABLE_NAME,
+            conditions = conditions.join(" AND "),
+            cursor = cursor,
+            size = TEXT_SEARCH_PAGE_SIZE,
+        );
+
+        let result = Storage::read(statement)?;
+        let hits = read(result)?;
+
+        let metadatas = self.get_metadatas_by_hashes(hits.iter().map(|hit| hit.metadata_hash.clone()).collect())?;
+
+        Ok(hits
+            .into_iter()
+            .filter_map(|hit| {
+                metadatas
+                    .iter()
+                    .find(|metadata| metadata.hash == hit.metadata_hash)
+                    .map(|metadata| MetadataTextMatch {
+                        metadata: metadata.clone(),
+                        rank: hit.score,
+                        snippet: hit.snippet,
+                    })
+            })
+            .collect())
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<MetadataTextHit>, ServiceError> {
+    let mut hits = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::MetadataTextHit(hit) => hits.push(hit),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        METADATA_FTS_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(hits)
+}
@@ -0,0 +1,76 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MetadataIndexPath {
+    pub meta_contract_id: String,
+    pub path: String,
+}
+
+/**
+ * Reads a dotted path such as `attributes.rarity` out of a content document.
+ * Numeric segments index into arrays
+ */
+pub fn extract_path<'a>(content: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(content, |value, segment| match value {
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => value.get(segment),
+    })
+}
+
+/**
+ * Text stored in the index for a content value, strings are kept unquoted
+ */
+pub fn index_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_nested_paths() {
+        let content = json!({"attributes": {"rarity": "rare", "level": 3}});
+
+        assert_eq!(extract_path(&content, "attributes.rarity"), Some(&json!("rare")));
+        assert_eq!(extract_path(&content, "attributes.level"), Some(&json!(3)));
+    }
+
+    #[test]
+    fn missing_paths_are_none() {
+        let content = json!({"attributes": {"rarity": "rare"}, "tags": ["a"]});
+
+        assert_eq!(extract_path(&content, "attributes.color"), None);
+        assert_eq!(extract_path(&content, "name.first"), None);
+        assert_eq!(extract_path(&content, "tags.1"), None);
+        assert_eq!(extract_path(&content, "tags.first"), None);
+    }
+
+    #[test]
+    fn numeric_segments_index_arrays() {
+        let content = json!({"traits": [{"type": "eyes"}, {"type": "hat"}]});
+
+        assert_eq!(extract_path(&content, "traits.1.type"), Some(&json!("hat")));
+        assert_eq!(extract_path(&content, "traits.0"), Some(&json!({"type": "eyes"})));
+    }
+
+    #[test]
+    fn values_are_indexed_as_text() {
+        assert_eq!(index_value(&json!("rare")), Some("rare".to_string()));
+        assert_eq!(index_value(&json!(3)), Some("3".to_string()));
+        assert_eq!(index_value(&json!(true)), Some("true".to_string()));
+        assert_eq!(index_value(&json!(null)), None);
+    }
+
+    #[test]
+    fn non_scalar_values_are_kept_as_json() {
+        assert_eq!(index_value(&json!(["a", "b"])), Some("[\"a\",\"b\"]".to_string()));
+        assert_eq!(index_value(&json!({"a": 1})), Some("{\"a\":1}".to_string()));
+    }
+}
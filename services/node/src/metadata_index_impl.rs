@@ -0,0 +1,141 @@
+use serde_json::Value;
+
+use crate::defaults::{METADATA_INDEX_PATHS_TABLE_NAME, METADATA_INDEX_TABLE_NAME};
+use crate::error::ServiceError;
+use crate::metadata_index::{extract_path, index_value};
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+
+impl Storage {
+    pub fn create_metadata_index_tables(&self) {
+        let statements = vec![
+            format!(
+                "
+                CREATE TABLE IF NOT EXISTS {} (
+                    meta_contract_id TEXT not null,
+                    path TEXT not null,
+                    PRIMARY KEY (meta_contract_id, path)
+                )",
+                METADATA_INDEX_PATHS_TABLE_NAME
+            ),
+            format!(
+                "
+                CREATE TABLE IF NOT EXISTS {} (
+                    metadata_hash TEXT not null,
+                    meta_contract_id TEXT not null,
+                    path TEXT not null,
+                    value NUMERIC null,
+                    PRIMARY KEY (metadata_hash, path)
+                )",
+                METADATA_INDEX_TABLE_NAME
+            ),
+            format!(
+                "CREATE INDEX IF NOT EXISTS {}_path_value ON {} (path, value)",
+                METADATA_INDEX_TABLE_NAME, METADATA_INDEX_TABLE_NAME
+            ),
+        ];
+
+        for statement in statements {
+            if let Err(error) = Storage::execute(statement) {
+                println!("create_metadata_index_tables error: {}", error);
+            }
+        }
+    }
+
+    /**
+     * Replaces the JSON paths indexed for the metadata of a meta contract.
+     * Values extracted for the previous paths are dropped, the records have to be indexed again
+     */
+    pub fn set_index_paths(&self, meta_contract_id: String, paths: Vec<String>) -> Result<(), ServiceError> {
+        Storage::execute(format!(
+            "DELETE FROM {} WHERE meta_contract_id = '{}'",
+            METADATA_INDEX_PATHS_TABLE_NAME, meta_contract_id
+        ))?;
+
+        Storage::execute(format!(
+            "DELETE FROM {} WHERE meta_contract_id = '{}'",
+            METADATA_INDEX_TABLE_NAME, meta_contract_id
+        ))?;
+
+        for path in paths {
+            Storage::execute(format!(
+                "insert into {} (meta_contract_id, path) values ('{}', '{}')",
+                METADATA_INDEX_PATHS_TABLE_NAME,
+                meta_contract_id,
+                Storage::escape(&path),
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_index_paths(&self, meta_contract_id: String) -> Result<Vec<String>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE meta_contract_id = '{}'",
+            METADATA_INDEX_PATHS_TABLE_NAME, meta_contract_id
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+
+    /**
+     * Extracts the indexed paths of the meta contract out of a block content
+     * and stores them for `search_metadatas`, replacing the previous values
+     */
+    pub fn index_metadata_content(
+        &self,
+        metadata_hash: String,
+        meta_contract_id: String,
+        content: &str,
+    ) -> Result<(), ServiceError> {
+        let paths = self.get_index_paths(meta_contract_id.clone())?;
+
+        if paths.is_empty() {
+            return Ok(());
+        }
+
+        let document: Value = serde_json::from_str(content).unwrap_or(Value::String(content.to_string()));
+
+        Storage::execute(format!(
+            "DELETE FROM {} WHERE metadata_hash = '{}'",
+            METADATA_INDEX_TABLE_NAME, metadata_hash
+        ))?;
+
+        for path in paths {
+            let value = extract_path(&document, &path).and_then(index_value);
+
+            if let Some(value) = value {
+                Storage::execute(format!(
+                    "insert into {} (metadata_hash, meta_contract_id, path, value) values ('{}', '{}', '{}', '{}')",
+                    METADATA_INDEX_TABLE_NAME,
+                    metadata_hash,
+                    meta_contract_id,
+                    Storage::escape(&path),
+                    Storage::escape(&value),
+                ))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<String>, ServiceError> {
+    let mut paths = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::MetadataIndexPath(index_path) => paths.push(index_path.path),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        METADATA_INDEX_PATHS_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(paths)
+}
@@ -0,0 +1,35 @@
+use marine_rs_sdk::marine;
+use serde::Deserialize;
+
+#[marine]
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MetadataVersion {
+    pub metadata_hash: String,
+    pub cid: String,
+    pub previous_cid: String,
+    pub transaction_hash: String,
+    pub method: String,
+    pub timestamp: u64,
+    pub content: String,
+}
+
+impl MetadataVersion {
+    pub fn new(
+        metadata_hash: String,
+        cid: String,
+        previous_cid: String,
+        transaction_hash: String,
+        method: String,
+        timestamp: u64,
+    ) -> Self {
+        Self {
+            metadata_hash,
+            cid,
+            previous_cid,
+            transaction_hash,
+            method,
+            timestamp,
+            content: "".to_string(),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::defaults::{METADATAS_TABLE_NAME, METADATA_VERSIONS_TABLE_NAME, METHOD_METADATA, TRANSACTIONS_TABLE_NAME};
+use crate::error::ServiceError;
+use crate::error::ServiceError::InternalError;
+use crate::metadata_versions::MetadataVersion;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+
+impl Storage {
+    pub fn create_metadata_versions_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                metadata_hash TEXT not null,
+                cid TEXT not null,
+                previous_cid TEXT null,
+                transaction_hash TEXT null,
+                method TEXT not null default '',
+                timestamp INTEGER not null,
+                PRIMARY KEY (metadata_hash, cid, transaction_hash)
+            )",
+            METADATA_VERSIONS_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_metadata_versions_table error: {}", error);
+        }
+    }
+
+    /**
+     * Records a CID change of a metadata record.
+     * Only the CID is kept, the content itself stays on IPFS. A deletion is recorded with an empty CID
+     */
+    pub fn write_metadata_version(&self, version: MetadataVersion) -> Result<(), ServiceError> {
+        let s = format!(
+            "insert into {} (metadata_hash, cid, previous_cid, transaction_hash, method, timestamp) values ('{}', '{}', '{}', '{}', '{}', '{}')",
+            METADATA_VERSIONS_TABLE_NAME,
+            version.metadata_hash,
+            version.cid,
+            version.previous_cid,
+            version.transaction_hash,
+            version.method,
+            version.timestamp,
+        );
+
+        let result = Storage::execute(s);
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                log::info!("{}", e);
+                Err(InternalError(e.to_string()))
+            }
+        }
+    }
+
+    /**
+     * Records written before versions were kept get their current CID as first version,
+     * dated by the last transaction that published them. A no-op once every record has one
+     */
+    pub fn backfill_metadata_versions(&self) -> Result<(), ServiceError> {
+        let last_transaction = format!(
+            "FROM {transactions} t WHERE t.data_key = m.data_key AND t.meta_contract_id = m.meta_contract_id
+            AND t.public_key = m.public_key AND t.alias = m.alias AND t.version = m.version ORDER BY t.timestamp DESC LIMIT 1",
+            transactions = TRANSACTIONS_TABLE_NAME
+        );
+
+        let statement = format!(
+            "insert or ignore into {versions} (metadata_hash, cid, previous_cid, transaction_hash, method, timestamp)
+            SELECT m.hash, m.cid, '', COALESCE((SELECT t.hash {last}), ''), '{}', COALESCE((SELECT t.timestamp {last}), 0)
+            FROM {metadatas} m WHERE m.cid != '' AND NOT EXISTS (SELECT 1 FROM {versions} v WHERE v.metadata_hash = m.hash)",
+            METHOD_METADATA,
+            last = last_transaction,
+            versions = METADATA_VERSIONS_TABLE_NAME,
+            metadatas = METADATAS_TABLE_NAME
+        );
+
+        Storage::execute(statement)?;
+        Ok(())
+    }
+
+    /**
+     * Versions of a metadata record, newest first unless `sort` is "asc".
+     * `limit` 0 returns every version after `offset`
+     */
+    pub fn get_metadata_versions(
+        &self,
+        metadata_hash: String,
+        sort: String,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<MetadataVersion>, ServiceError> {
+        let direction = if sort.eq_ignore_ascii_case("asc") {
+            "ASC"
+        } else {
+            "DESC"
+        };
+
+        let mut limit_str = "".to_string();
+        if limit > 0 {
+            limit_str = format!("LIMIT {},{}", offset, limit);
+        }
+
+        // content is not stored, it is filled from IPFS on demand
+        let statement = format!(
+            "SELECT *, '' AS content FROM {} WHERE metadata_hash = '{}' ORDER BY timestamp {}, rowid {} {}",
+            METADATA_VERSIONS_TABLE_NAME, metadata_hash, direction, direction, limit_str
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+
+    /**
+     * First version a transaction wrote on a metadata record, when it wrote one
+     */
+    pub fn get_metadata_version_by_transaction(
+        &self,
+        metadata_hash: String,
+        transaction_hash: String,
+    ) -> Result<Option<MetadataVersion>, ServiceError> {
+        let statement = format!(
+            "SELECT *, '' AS content FROM {} WHERE metadata_hash = '{}' AND transaction_hash = '{}' ORDER BY rowid ASC LIMIT 1",
+            METADATA_VERSIONS_TABLE_NAME, metadata_hash, transaction_hash
+        );
+
+        let result = Storage::read(statement)?;
+        Ok(read(result)?.first().cloned())
+    }
+
+    /**
+     * Version of a metadata record that was current at the given timestamp
+     */
+    pub fn get_metadata_version_at(
+        &self,
+        metadata_hash: String,
+        timestamp: u64,
+    ) -> Result<MetadataVersion, ServiceError> {
+        let statement = format!(
+            "SELECT *, '' AS content FROM {} WHERE metadata_hash = '{}' AND timestamp <= {} ORDER BY timestamp DESC, rowid DESC LIMIT 1",
+            METADATA_VERSIONS_TABLE_NAME, metadata_hash, timestamp
+        );
+
+        let result = Storage::read(statement)?;
+        match read(result) {
+            Ok(versions) => versions
+                .first()
+                .cloned()
+                .ok_or_else(|| ServiceError::RecordNotFound(format!("No version at {}", timestamp))),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<MetadataVersion>, ServiceError> {
+    let mut versions = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::MetadataVersion(version) => versions.push(version),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        METADATA_VERSIONS_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(versions)
+}
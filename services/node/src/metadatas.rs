@@ -1,6 +1,8 @@
 use marine_rs_sdk::marine;
 use sha2::{Digest, Sha256};
 use serde::{Serialize, Deserialize};
+
+use crate::normalize::{normalize_address, normalize_chain_id, normalize_token_id};
 #[marine]
 #[derive(Debug, Default, Clone, Deserialize)]
 pub struct Metadata {
@@ -13,6 +15,8 @@ pub struct Metadata {
     pub public_key: String,
     pub version: String,
     pub loose: i64,
+    pub tombstone: String,
+    pub asset_id: String,
 }
 
 impl Metadata {
@@ -36,6 +40,8 @@ impl Metadata {
             public_key,
             version,
             loose,
+            tombstone: "".to_string(),
+            asset_id: "".to_string(),
         }
     }
     pub fn generate_hash(
@@ -60,6 +66,16 @@ impl Metadata {
       bs58::encode(hasher.finalize()).into_string()
   }
 
+    /**
+     * Collection records are keyed by the token_key instead of a token data_key
+     */
+    pub fn is_collection(&self) -> bool {
+        self.data_key == self.token_key
+    }
+
+    /**
+     * Chain id and address are normalized first, so every spelling of a contract gets the same key
+     */
     pub fn generate_token_key(
         chain_id: String,
         token_address: String,
@@ -68,23 +84,37 @@ impl Metadata {
         hasher.update(
             format!(
                 "{}{}",
-                chain_id, token_address,
+                normalize_chain_id(&chain_id),
+                normalize_address(&token_address),
             )
             .as_bytes(),
         );
         bs58::encode(hasher.finalize()).into_string()
     }
 
+    /**
+     * Key of the records of a token. Without a token id the records belong to the
+     * whole collection and are keyed by its token_key. Inputs are normalized like the token_key's,
+     * token ids as decimals
+     */
     pub fn generate_data_key(
         chain_id: String,
         token_address: String,
         token_id: String,
     ) -> String {
+        let token_id = normalize_token_id(&token_id);
+
+        if token_id.is_empty() {
+            return Self::generate_token_key(chain_id, token_address);
+        }
+
         let mut hasher = Sha256::new();
         hasher.update(
             format!(
                 "{}{}{}",
-                chain_id, token_address, token_id,
+                normalize_chain_id(&chain_id),
+                normalize_address(&token_address),
+                token_id,
             )
             .as_bytes(),
         );
@@ -92,6 +122,18 @@ impl Metadata {
     }
 }
 
+/**
+ * Block content of a metadata record. With inheritance, `content` is the collection content
+ * overridden by the record's own, and `collection_cid` the collection block it came from
+ */
+#[marine]
+#[derive(Debug, Default, Clone)]
+pub struct MetadataContent {
+    pub metadata: Metadata,
+    pub content: String,
+    pub collection_cid: String,
+}
+
 #[marine]
 #[derive(Debug, Clone)]
 pub struct FinalMetadata {
@@ -121,3 +163,9 @@ pub struct MetadataOrdering {
 pub struct SerdeMetadata {
   pub loose: i64,
 }
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SerdeDelete {
+  #[serde(default)]
+  pub unpin: bool,
+}
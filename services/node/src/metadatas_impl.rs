@@ -1,4 +1,4 @@
-use crate::defaults::METADATAS_TABLE_NAME;
+use crate::defaults::{ASSETS_TABLE_NAME, CONTENT_COLUMN_PREFIX, METADATAS_TABLE_NAME, METADATA_INDEX_TABLE_NAME, METADATA_VERSIONS_TABLE_NAME};
 use crate::error::ServiceError;
 use crate::error::ServiceError::RecordNotFound;
 use crate::metadatas::{Metadata, MetadataQuery, MetadataOrdering};
@@ -18,7 +18,9 @@ impl Storage {
                 cid TEXT null,
                 public_key TEXT not null,
                 version varchar(255) null,
-                loose INTEGER CHECK(loose IN (0, 1))
+                loose INTEGER CHECK(loose IN (0, 1)),
+                tombstone TEXT not null default '',
+                asset_id TEXT not null default ''
             )",
             METADATAS_TABLE_NAME
         );
@@ -28,15 +30,18 @@ impl Storage {
         if let Err(error) = result {
             println!("create_transactions_table error: {}", error);
         }
+
+        Storage::add_column(METADATAS_TABLE_NAME, "tombstone", "TEXT not null default ''");
+        Storage::add_column(METADATAS_TABLE_NAME, "asset_id", "TEXT not null default ''");
     }
 
     /**
      * Upon creation of metadata record, it doesnt write metadata CID to the record.
-     * Its focusing on creating schema
+     * Its focusing on creating schema. Without an asset id, the one recorded for the data_key is used
      */
     pub fn write_metadata(&self, metadata: Metadata) -> Result<(), ServiceError> {
         let s = format!(
-            "insert into {} (hash, token_key, data_key, meta_contract_id, alias, cid, public_key, version, loose) values ('{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}')",
+            "insert into {} (hash, token_key, data_key, meta_contract_id, alias, cid, public_key, version, loose, tombstone, asset_id) values ('{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', COALESCE(NULLIF('{}', ''), (SELECT asset_id FROM {} WHERE data_key = '{}'), ''))",
             METADATAS_TABLE_NAME,
             metadata.hash,
             metadata.token_key,
@@ -47,6 +52,10 @@ impl Storage {
             metadata.public_key,
             metadata.version,
             metadata.loose,
+            metadata.tombstone,
+            Storage::escape(&metadata.asset_id),
+            ASSETS_TABLE_NAME,
+            metadata.data_key,
         );
 
         log::info!("{}", s);
@@ -112,7 +121,7 @@ impl Storage {
         version: String,
     ) -> Result<Vec<Metadata>, ServiceError> {
         let statement = format!(
-            "SELECT * FROM {} WHERE data_key = '{}' AND version = '{}'",
+            "SELECT * FROM {} WHERE data_key = '{}' AND version = '{}' AND tombstone = ''",
             METADATAS_TABLE_NAME,
             data_key.clone(),
             version.clone()
@@ -152,6 +161,73 @@ impl Storage {
       }
     }
 
+    /**
+     * Every record written under a meta contract, deleted ones excluded
+     */
+    pub fn get_live_metadatas_by_meta_contract_id(&self, meta_contract_id: String) -> Result<Vec<Metadata>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE meta_contract_id = '{}' AND tombstone = ''",
+            METADATAS_TABLE_NAME, meta_contract_id
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+
+    /**
+     * Live records a transaction wrote a version of
+     */
+    pub fn get_live_metadatas_by_transaction(&self, transaction_hash: String) -> Result<Vec<Metadata>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE tombstone = '' AND hash IN (SELECT metadata_hash FROM {} WHERE transaction_hash = '{}')",
+            METADATAS_TABLE_NAME, METADATA_VERSIONS_TABLE_NAME, transaction_hash
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+
+    /**
+     * Brings back a record tombstoned by a reverted event under a new CID
+     */
+    pub fn restore_metadata(&self, hash: String, cid: String) -> Result<(), ServiceError> {
+        let s = format!(
+            "update {} set tombstone = '', cid = '{}' where hash = '{}'",
+            METADATAS_TABLE_NAME, cid, hash
+        );
+
+        Storage::execute(s)?;
+        Ok(())
+    }
+
+    /**
+     * Marks a metadata record as deleted by the given transaction. The row and its history are kept
+     */
+    pub fn tombstone_metadata(&self, hash: String, transaction_hash: String) -> Result<(), ServiceError> {
+        let s = format!(
+            "update {} set tombstone = '{}' where hash = '{}'",
+            METADATAS_TABLE_NAME, transaction_hash, hash
+        );
+
+        Storage::execute(s)?;
+        Ok(())
+    }
+
+    pub fn get_metadatas_by_hashes(&self, hashes: Vec<String>) -> Result<Vec<Metadata>, ServiceError> {
+        if hashes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let statement = format!(
+            "SELECT * FROM {} WHERE hash IN ('{}')",
+            METADATAS_TABLE_NAME,
+            hashes.join("', '"),
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+
     // pub fn get_metadata_by_datakey(&self, data_key: String) -> Result<Vec<Metadata>, ServiceError> {
     //     let mut statement = self.connection.prepare(f!(
     //         "SELECT * FROM {METADATAS_TABLE_NAME} WHERE data_key = ?"
@@ -170,15 +246,11 @@ impl Storage {
 
     pub fn search_metadatas(&self, query: Vec<MetadataQuery>, ordering: Vec<MetadataOrdering>, from: u32, to: u32) -> Result<Vec<Metadata>, ServiceError> {
       
-      let mut query_str = "".to_string();
       let mut ordering_str = "".to_string();
       let mut limit_str = "".to_string();
 
-      if query.len() > 0 {
-        let queries: Vec<String> = query.into_iter().map(|param| format!("{} {} '{}'", param.column, param.op, param.query)).collect();
-
-        query_str = format!("WHERE {}",queries.join(" AND "));
-      }
+      let queries = metadata_conditions(query);
+      let query_str = format!("WHERE {}",queries.join(" AND "));
 
       if ordering.len() > 0 {
         let orders: Vec<String> = ordering.into_iter().map(|param| format!("{} {}", param.column, param.sort)).collect();
@@ -203,6 +275,31 @@ impl Storage {
     }
 }
 
+/**
+ * SQL conditions for metadata queries. Columns prefixed with `content.` are JSON paths
+ * answered by the index extracted from the block content.
+ * Deleted records are left out unless the query filters on `tombstone` itself
+ */
+pub fn metadata_conditions(query: Vec<MetadataQuery>) -> Vec<String> {
+  let mut conditions = Vec::new();
+
+  if !query.iter().any(|param| param.column == "tombstone") {
+    conditions.push(format!("{}.tombstone = ''", METADATAS_TABLE_NAME));
+  }
+
+  for param in query {
+    conditions.push(match param.column.strip_prefix(CONTENT_COLUMN_PREFIX) {
+      Some(path) => format!(
+        "{}.hash IN (SELECT metadata_hash FROM {} WHERE path = '{}' AND value {} '{}')",
+        METADATAS_TABLE_NAME, METADATA_INDEX_TABLE_NAME, path, param.op, param.query
+      ),
+      None => format!("{}.{} {} '{}'", METADATAS_TABLE_NAME, param.column, param.op, param.query),
+    });
+  }
+
+  conditions
+}
+
 pub fn read(result: RQLiteResult) -> Result<Vec<Metadata>, ServiceError> {
   let mut metas = Vec::new();
 
@@ -0,0 +1,236 @@
+use crate::data_types::ForkPolicyResult;
+use crate::defaults::NATIVE_META_CONTRACT_PREFIX;
+use crate::metadatas::{FinalMetadata, Metadata, SerdeMetadata};
+use crate::transaction::Transaction;
+
+/**
+ * What a native policy sees of a metadata transaction: the live records of its data_key
+ * and the owner of its token_key, empty when the token has no meta contract
+ */
+pub struct NativeContext<'a> {
+    pub transaction: &'a Transaction,
+    pub metadatas: &'a [Metadata],
+    pub token_owner: String,
+}
+
+/**
+ * Meta contract evaluated inside the node instead of a separate service.
+ * It answers like `on_execute`: the records to write, or the reason the transaction is refused
+ */
+pub trait NativePolicy {
+    fn execute(&self, context: &NativeContext) -> Result<Vec<FinalMetadata>, String>;
+
+    /** Records of native meta contracts can be forked freely unless a policy says otherwise */
+    fn fork_policy(&self) -> ForkPolicyResult {
+        ForkPolicyResult {
+            allowed: true,
+            ..Default::default()
+        }
+    }
+}
+
+/** Only the owner of the token_key can write */
+pub struct OwnerOnly;
+
+/** Records can be added but never rewritten */
+pub struct AppendOnly;
+
+/** The first key writing an alias keeps it */
+pub struct FirstWriteWins;
+
+/** A key can hold at most this many aliases on a data_key */
+pub struct MaxAliases(pub usize);
+
+impl NativePolicy for OwnerOnly {
+    fn execute(&self, context: &NativeContext) -> Result<Vec<FinalMetadata>, String> {
+        if context.token_owner.is_empty() || context.token_owner != context.transaction.public_key {
+            return Err(format!("{} is not the token owner", context.transaction.public_key));
+        }
+
+        Ok(vec![write(context.transaction)])
+    }
+}
+
+impl NativePolicy for AppendOnly {
+    fn execute(&self, context: &NativeContext) -> Result<Vec<FinalMetadata>, String> {
+        let exists = context.metadatas.iter().any(|metadata| {
+            metadata.public_key == context.transaction.public_key && metadata.alias == context.transaction.alias
+        });
+
+        if exists {
+            return Err(format!("{} is already written", context.transaction.alias));
+        }
+
+        Ok(vec![write(context.transaction)])
+    }
+}
+
+impl NativePolicy for FirstWriteWins {
+    fn execute(&self, context: &NativeContext) -> Result<Vec<FinalMetadata>, String> {
+        let taken = context.metadatas.iter().any(|metadata| {
+            metadata.alias == context.transaction.alias && metadata.public_key != context.transaction.public_key
+        });
+
+        if taken {
+            return Err(format!("{} belongs to another key", context.transaction.alias));
+        }
+
+        Ok(vec![write(context.transaction)])
+    }
+}
+
+impl NativePolicy for MaxAliases {
+    fn execute(&self, context: &NativeContext) -> Result<Vec<FinalMetadata>, String> {
+        let owned: Vec<&Metadata> = context
+            .metadatas
+            .iter()
+            .filter(|metadata| metadata.public_key == context.transaction.public_key)
+            .collect();
+
+        let is_new = !owned.iter().any(|metadata| metadata.alias == context.transaction.alias);
+
+        if is_new && owned.len() >= self.0 {
+            return Err(format!("{} already holds {} aliases", context.transaction.public_key, self.0));
+        }
+
+        Ok(vec![write(context.transaction)])
+    }
+}
+
+/**
+ * Native policies are named `native:owner-only`, `native:append-only`, `native:first-write-wins`
+ * and `native:max-aliases:<n>`
+ */
+pub fn native_policy(meta_contract_id: &str) -> Option<Box<dyn NativePolicy>> {
+    let name = meta_contract_id.strip_prefix(NATIVE_META_CONTRACT_PREFIX)?;
+
+    match name {
+        "owner-only" => Some(Box::new(OwnerOnly)),
+        "append-only" => Some(Box::new(AppendOnly)),
+        "first-write-wins" => Some(Box::new(FirstWriteWins)),
+        _ => {
+            let max = name.strip_prefix("max-aliases:")?.parse::<usize>().ok()?;
+            Some(Box::new(MaxAliases(max)))
+        }
+    }
+}
+
+pub fn is_native(meta_contract_id: &str) -> bool {
+    meta_contract_id.starts_with(NATIVE_META_CONTRACT_PREFIX)
+}
+
+/**
+ * The transaction content as its signer's record, like a meta contract that accepts it unchanged
+ */
+fn write(transaction: &Transaction) -> FinalMetadata {
+    let loose = serde_json::from_str::<SerdeMetadata>(&transaction.mcdata)
+        .map(|metadata| metadata.loose)
+        .unwrap_or(1);
+
+    FinalMetadata {
+        public_key: transaction.public_key.clone(),
+        alias: transaction.alias.clone(),
+        content: transaction.data.clone(),
+        loose,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(public_key: &str, alias: &str) -> Transaction {
+        Transaction {
+            public_key: public_key.to_string(),
+            alias: alias.to_string(),
+            data: "{\"name\":\"a\"}".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn record(public_key: &str, alias: &str) -> Metadata {
+        Metadata {
+            public_key: public_key.to_string(),
+            alias: alias.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn context<'a>(transaction: &'a Transaction, metadatas: &'a [Metadata], token_owner: &str) -> NativeContext<'a> {
+        NativeContext {
+            transaction,
+            metadatas,
+            token_owner: token_owner.to_string(),
+        }
+    }
+
+    #[test]
+    fn resolves_reserved_names() {
+        assert!(native_policy("native:owner-only").is_some());
+        assert!(native_policy("native:append-only").is_some());
+        assert!(native_policy("native:first-write-wins").is_some());
+        assert!(native_policy("native:max-aliases:3").is_some());
+        assert!(native_policy("native:max-aliases:x").is_none());
+        assert!(native_policy("native:unknown").is_none());
+        assert!(native_policy("owner-only").is_none());
+        assert!(is_native("native:anything"));
+        assert!(!is_native("8ea1e9bb-3113-4d04-bf02-cb92afa6d424"));
+    }
+
+    #[test]
+    fn owner_only_accepts_the_token_owner() {
+        let tx = transaction("0xowner", "main");
+
+        let written = OwnerOnly.execute(&context(&tx, &[], "0xowner")).unwrap();
+        assert_eq!(written.len(), 1);
+        assert_eq!(written[0].public_key, "0xowner");
+        assert_eq!(written[0].content, tx.data);
+
+        assert!(OwnerOnly.execute(&context(&tx, &[], "0xother")).is_err());
+        assert!(OwnerOnly.execute(&context(&tx, &[], "")).is_err());
+    }
+
+    #[test]
+    fn append_only_refuses_rewrites() {
+        let tx = transaction("0xa", "main");
+
+        assert!(AppendOnly.execute(&context(&tx, &[record("0xb", "main")], "")).is_ok());
+        assert!(AppendOnly.execute(&context(&tx, &[record("0xa", "main")], "")).is_err());
+    }
+
+    #[test]
+    fn first_write_wins_keeps_aliases_with_their_first_key() {
+        let tx = transaction("0xa", "main");
+
+        assert!(FirstWriteWins.execute(&context(&tx, &[record("0xa", "main")], "")).is_ok());
+        assert!(FirstWriteWins.execute(&context(&tx, &[record("0xb", "main")], "")).is_err());
+        assert!(FirstWriteWins.execute(&context(&tx, &[record("0xb", "other")], "")).is_ok());
+    }
+
+    #[test]
+    fn max_aliases_limits_new_aliases_only() {
+        let records = [record("0xa", "one"), record("0xa", "two"), record("0xb", "three")];
+
+        let new_alias = transaction("0xa", "three");
+        assert!(MaxAliases(2).execute(&context(&new_alias, &records, "")).is_err());
+        assert!(MaxAliases(3).execute(&context(&new_alias, &records, "")).is_ok());
+
+        let existing_alias = transaction("0xa", "two");
+        assert!(MaxAliases(2).execute(&context(&existing_alias, &records, "")).is_ok());
+    }
+
+    #[test]
+    fn writes_are_loose_unless_mcdata_says_otherwise() {
+        let mut tx = transaction("0xa", "main");
+        assert_eq!(write(&tx).loose, 1);
+
+        tx.mcdata = "{\"loose\":0}".to_string();
+        assert_eq!(write(&tx).loose, 0);
+    }
+
+    #[test]
+    fn native_policies_allow_forks() {
+        assert!(OwnerOnly.fork_policy().allowed);
+        assert!(MaxAliases(1).fork_policy().allowed);
+    }
+}
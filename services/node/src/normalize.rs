@@ -0,0 +1,166 @@
+use marine_rs_sdk::marine;
+use serde::Deserialize;
+
+/**
+ * Chain identifiers are hashed as CAIP-2. Bare EVM chain ids, decimal or hex,
+ * become `eip155:<decimal>`
+ */
+pub fn normalize_chain_id(chain_id: &str) -> String {
+    let chain_id = chain_id.trim();
+
+    if let Some((namespace, reference)) = chain_id.split_once(':') {
+        let namespace = namespace.to_lowercase();
+
+        if namespace == "eip155" {
+            if let Some(reference) = to_decimal(reference) {
+                return format!("eip155:{}", reference);
+            }
+        }
+
+        return format!("{}:{}", namespace, reference);
+    }
+
+    match to_decimal(chain_id) {
+        Some(reference) => format!("eip155:{}", reference),
+        None => chain_id.to_string(),
+    }
+}
+
+/**
+ * EVM addresses, checksummed or not, are hashed lowercase. Other addresses can be case
+ * sensitive and are only trimmed
+ */
+pub fn normalize_address(address: &str) -> String {
+    let address = address.trim();
+
+    match address.strip_prefix("0x").or_else(|| address.strip_prefix("0X")) {
+        Some(hex) if !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+            format!("0x{}", hex.to_lowercase())
+        }
+        _ => address.to_string(),
+    }
+}
+
+/**
+ * Token ids are hashed as decimals without leading zeros. Hex ids are converted,
+ * whatever their size
+ */
+pub fn normalize_token_id(token_id: &str) -> String {
+    let token_id = token_id.trim();
+
+    to_decimal(token_id).unwrap_or_else(|| token_id.to_string())
+}
+
+fn to_decimal(value: &str) -> Option<String> {
+    let value = value.trim();
+
+    if let Some(hex) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        return hex_to_decimal(hex);
+    }
+
+    if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let trimmed = value.trim_start_matches('0');
+
+    if trimmed.is_empty() {
+        Some("0".to_string())
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn hex_to_decimal(hex: &str) -> Option<String> {
+    if hex.is_empty() {
+        return None;
+    }
+
+    // base 10 digits, least significant first
+    let mut digits: Vec<u32> = vec![0];
+
+    for c in hex.chars() {
+        let mut carry = c.to_digit(16)?;
+
+        for digit in digits.iter_mut() {
+            let value = *digit * 16 + carry;
+            *digit = value % 10;
+            carry = value / 10;
+        }
+
+        while carry > 0 {
+            digits.push(carry % 10);
+            carry /= 10;
+        }
+    }
+
+    while digits.len() > 1 && digits.last() == Some(&0) {
+        digits.pop();
+    }
+
+    Some(digits.iter().rev().map(|d| char::from_digit(*d, 10).unwrap_or('0')).collect())
+}
+
+/**
+ * Keys a set of transactions were published with
+ */
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct KeyCoordinates {
+    pub chain_id: String,
+    pub token_address: String,
+    pub token_id: String,
+    pub data_key: String,
+    pub token_key: String,
+}
+
+#[marine]
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    pub rekeyed: u32,
+    pub merged: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_hex_of_any_size() {
+        assert_eq!(hex_to_decimal("0").as_deref(), Some("0"));
+        assert_eq!(hex_to_decimal("ff").as_deref(), Some("255"));
+        assert_eq!(hex_to_decimal("00FF").as_deref(), Some("255"));
+        assert_eq!(
+            hex_to_decimal("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").as_deref(),
+            Some("115792089237316195423570985008687907853269984665640564039457584007913129639935")
+        );
+        assert_eq!(hex_to_decimal(""), None);
+        assert_eq!(hex_to_decimal("0xg"), None);
+    }
+
+    #[test]
+    fn normalizes_chain_ids() {
+        assert_eq!(normalize_chain_id("1"), "eip155:1");
+        assert_eq!(normalize_chain_id("0x89"), "eip155:137");
+        assert_eq!(normalize_chain_id(" EIP155:0x1 "), "eip155:1");
+        assert_eq!(normalize_chain_id("eip155:01"), "eip155:1");
+        assert_eq!(normalize_chain_id("solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp"), "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp");
+        assert_eq!(normalize_chain_id("mainnet"), "mainnet");
+    }
+
+    #[test]
+    fn normalizes_addresses() {
+        assert_eq!(normalize_address(" 0xAbCdEf "), "0xabcdef");
+        assert_eq!(normalize_address("0XABC"), "0xabc");
+        assert_eq!(normalize_address("0x"), "0x");
+        assert_eq!(normalize_address("So1anaAddress"), "So1anaAddress");
+    }
+
+    #[test]
+    fn normalizes_token_ids() {
+        assert_eq!(normalize_token_id("0x10"), "16");
+        assert_eq!(normalize_token_id("0016"), "16");
+        assert_eq!(normalize_token_id("000"), "0");
+        assert_eq!(normalize_token_id(""), "");
+        assert_eq!(normalize_token_id("abc"), "abc");
+    }
+}
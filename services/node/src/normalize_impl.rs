@@ -0,0 +1,240 @@
+use crate::defaults::{
+    CLONE_EDGES_TABLE_NAME, CRON_TABLE_NAME, CRON_TX_TABLE_NAME, GRANTS_TABLE_NAME, METADATAS_TABLE_NAME, METADATA_FREEZES_TABLE_NAME, METADATA_FTS_TABLE_NAME,
+    METADATA_INDEX_TABLE_NAME, METADATA_VERSIONS_TABLE_NAME, META_CONTRACT_TABLE_NAME, META_CONTRACT_BINDINGS_TABLE_NAME, META_CONTRACT_VERSIONS_TABLE_NAME, OWNERSHIP_TRANSFERS_TABLE_NAME,
+    TRANSACTIONS_TABLE_NAME,
+};
+use crate::error::ServiceError;
+use crate::metadatas::Metadata;
+use crate::metadatas_impl;
+use crate::normalize::{KeyCoordinates, MigrationReport};
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+
+impl Storage {
+    /**
+     * Moves records keyed before normalization to their normalized keys.
+     * When both exist, the two records are merged into the normalized one: histories are joined
+     * and the content that changed last is kept
+     */
+    pub fn migrate_normalized_keys(&self) -> Result<MigrationReport, ServiceError> {
+        let mut report = MigrationReport::default();
+
+        for coordinates in self.get_key_coordinates()? {
+            let token_key = Metadata::generate_token_key(coordinates.chain_id.clone(), coordinates.token_address.clone());
+
+            if token_key != coordinates.token_key {
+                self.rekey_token_key(&coordinates.token_key, &token_key)?;
+            }
+
+            if coordinates.data_key.is_empty() {
+                continue;
+            }
+
+            let data_key = Metadata::generate_data_key(
+                coordinates.chain_id,
+                coordinates.token_address,
+                coordinates.token_id,
+            );
+
+            if data_key == coordinates.data_key {
+                continue;
+            }
+
+            self.rekey_data_key(&coordinates.data_key, &data_key)?;
+
+            for legacy in self.get_all_metadatas_by_datakey(coordinates.data_key.clone())? {
+                let existing = self.get_owner_metadata(
+                    data_key.clone(),
+                    legacy.meta_contract_id.clone(),
+                    legacy.public_key.clone(),
+                    legacy.alias.clone(),
+                    legacy.version.clone(),
+                );
+
+                match existing {
+                    Ok(target) => {
+                        let legacy_is_newer = self.latest_version_timestamp(&legacy.hash)? > self.latest_version_timestamp(&target.hash)?;
+                        self.merge_metadata(&legacy, &target, legacy_is_newer)?;
+                        report.merged += 1;
+                    }
+                    Err(ServiceError::RecordNotFound(_)) => {
+                        let hash = Metadata::generate_hash(
+                            data_key.clone(),
+                            legacy.meta_contract_id.clone(),
+                            legacy.alias.clone(),
+                            legacy.public_key.clone(),
+                            legacy.version.clone(),
+                        );
+                        self.rekey_metadata(&legacy.hash, &hash, &data_key, &token_key)?;
+                        report.rekeyed += 1;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /**
+     * Keys of published transactions and of indexed events, whose keys were computed off-chain
+     */
+    pub(crate) fn get_key_coordinates(&self) -> Result<Vec<KeyCoordinates>, ServiceError> {
+        let statement = format!(
+            "SELECT DISTINCT chain_id, token_address, token_id, data_key, token_key FROM {} WHERE token_key != ''
+            UNION SELECT DISTINCT chain AS chain_id, address AS token_address, token_id, data_key, token_key FROM {} WHERE token_key != '' AND token_id != ''",
+            TRANSACTIONS_TABLE_NAME, CRON_TX_TABLE_NAME
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+
+    fn get_all_metadatas_by_datakey(&self, data_key: String) -> Result<Vec<Metadata>, ServiceError> {
+        let statement = format!("SELECT * FROM {} WHERE data_key = '{}'", METADATAS_TABLE_NAME, data_key);
+
+        let result = Storage::read(statement)?;
+        metadatas_impl::read(result)
+    }
+
+    fn latest_version_timestamp(&self, metadata_hash: &str) -> Result<u64, ServiceError> {
+        let versions = self.get_metadata_versions(metadata_hash.to_string(), "desc".to_string(), 0, 1)?;
+
+        Ok(versions.first().map(|version| version.timestamp).unwrap_or_default())
+    }
+
+    /**
+     * A binding already made on the normalized token_key wins over the legacy one
+     */
+    fn rekey_token_key(&self, old: &str, new: &str) -> Result<(), ServiceError> {
+        let statements = vec![
+            format!(
+                "update {table} set token_key = '{}' where token_key = '{}' AND NOT EXISTS (SELECT 1 FROM {table} WHERE token_key = '{}')",
+                new, old, new, table = META_CONTRACT_TABLE_NAME
+            ),
+            format!("delete from {} where token_key = '{}'", META_CONTRACT_TABLE_NAME, old),
+            format!("update or ignore {} set token_key = '{}' where token_key = '{}'", META_CONTRACT_VERSIONS_TABLE_NAME, new, old),
+            format!("update or ignore {} set token_key = '{}' where token_key = '{}'", META_CONTRACT_BINDINGS_TABLE_NAME, new, old),
+            format!("delete from {} where token_key = '{}'", META_CONTRACT_BINDINGS_TABLE_NAME, old),
+            format!("update {} set token_key = '{}' where token_key = '{}'", CRON_TABLE_NAME, new, old),
+            format!("update {} set token_key = '{}' where token_key = '{}'", METADATAS_TABLE_NAME, new, old),
+            format!("update {} set token_key = '{}' where token_key = '{}'", TRANSACTIONS_TABLE_NAME, new, old),
+            format!("update {} set token_key = '{}' where token_key = '{}'", GRANTS_TABLE_NAME, new, old),
+            format!("update {} set subject = '{}' where subject = '{}'", OWNERSHIP_TRANSFERS_TABLE_NAME, new, old),
+        ];
+
+        for statement in statements {
+            Storage::execute(statement)?;
+        }
+
+        Ok(())
+    }
+
+    fn rekey_data_key(&self, old: &str, new: &str) -> Result<(), ServiceError> {
+        let statements = vec![
+            format!("update {} set data_key = '{}' where data_key = '{}'", TRANSACTIONS_TABLE_NAME, new, old),
+            format!("update {} set data_key = '{}' where data_key = '{}'", GRANTS_TABLE_NAME, new, old),
+            format!("update {} set data_key = '{}' where data_key = '{}'", CRON_TX_TABLE_NAME, new, old),
+            // a freeze on either spelling keeps the merged record frozen
+            format!("update or ignore {} set data_key = '{}' where data_key = '{}'", METADATA_FREEZES_TABLE_NAME, new, old),
+            format!("delete from {} where data_key = '{}'", METADATA_FREEZES_TABLE_NAME, old),
+        ];
+
+        for statement in statements {
+            Storage::execute(statement)?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn rekey_metadata(&self, old_hash: &str, new_hash: &str, data_key: &str, token_key: &str) -> Result<(), ServiceError> {
+        let statements = vec![
+            format!(
+                "update {} set hash = '{}', data_key = '{}', token_key = '{}' where hash = '{}'",
+                METADATAS_TABLE_NAME, new_hash, data_key, token_key, old_hash
+            ),
+            format!("update {} set metadata_hash = '{}' where metadata_hash = '{}'", METADATA_VERSIONS_TABLE_NAME, new_hash, old_hash),
+            format!("update {} set metadata_hash = '{}' where metadata_hash = '{}'", METADATA_INDEX_TABLE_NAME, new_hash, old_hash),
+            format!("update {} set metadata_hash = '{}' where metadata_hash = '{}'", METADATA_FTS_TABLE_NAME, new_hash, old_hash),
+            format!("update {} set subject = '{}' where subject = '{}'", OWNERSHIP_TRANSFERS_TABLE_NAME, new_hash, old_hash),
+            format!("update {} set origin_hash = '{}' where origin_hash = '{}'", CLONE_EDGES_TABLE_NAME, new_hash, old_hash),
+            format!("update {} set clone_hash = '{}' where clone_hash = '{}'", CLONE_EDGES_TABLE_NAME, new_hash, old_hash),
+        ];
+
+        for statement in statements {
+            Storage::execute(statement)?;
+        }
+
+        Ok(())
+    }
+
+    fn merge_metadata(&self, legacy: &Metadata, target: &Metadata, legacy_is_newer: bool) -> Result<(), ServiceError> {
+        // versions both records share are kept once, the legacy rows never outlive the merge
+        let mut statements = vec![
+            format!(
+                "insert or ignore into {table} (metadata_hash, cid, previous_cid, transaction_hash, method, timestamp)
+                SELECT '{}', cid, previous_cid, transaction_hash, method, timestamp FROM {table} WHERE metadata_hash = '{}'",
+                target.hash, legacy.hash, table = METADATA_VERSIONS_TABLE_NAME
+            ),
+            format!("delete from {} where metadata_hash = '{}'", METADATA_VERSIONS_TABLE_NAME, legacy.hash),
+            format!(
+                "update {} set subject = '{}' where subject = '{}'",
+                OWNERSHIP_TRANSFERS_TABLE_NAME, target.hash, legacy.hash
+            ),
+        ];
+
+        for column in ["origin_hash", "clone_hash"] {
+            statements.push(format!(
+                "update or ignore {table} set {column} = '{}' where {column} = '{}'",
+                target.hash, legacy.hash, table = CLONE_EDGES_TABLE_NAME, column = column
+            ));
+            statements.push(format!("delete from {} where {} = '{}'", CLONE_EDGES_TABLE_NAME, column, legacy.hash));
+        }
+
+        // the search indexes follow the content that is kept
+        let (kept, dropped) = if legacy_is_newer {
+            statements.push(format!(
+                "update {} set cid = '{}' where hash = '{}'",
+                METADATAS_TABLE_NAME, legacy.cid, target.hash
+            ));
+            (&legacy.hash, &target.hash)
+        } else {
+            (&target.hash, &legacy.hash)
+        };
+
+        for table in [METADATA_INDEX_TABLE_NAME, METADATA_FTS_TABLE_NAME] {
+            statements.push(format!("delete from {} where metadata_hash = '{}'", table, dropped));
+            statements.push(format!(
+                "update {} set metadata_hash = '{}' where metadata_hash = '{}'",
+                table, target.hash, kept
+            ));
+        }
+
+        statements.push(format!("delete from {} where hash = '{}'", METADATAS_TABLE_NAME, legacy.hash));
+
+        for statement in statements {
+            Storage::execute(statement)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<KeyCoordinates>, ServiceError> {
+    let mut coordinates = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::KeyCoordinates(row) => coordinates.push(row),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        TRANSACTIONS_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(coordinates)
+}
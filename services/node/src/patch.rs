@@ -0,0 +1,185 @@
+use json_patch::Patch;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::block::Block;
+use crate::defaults::{PATCH_MODE_JSON_PATCH, PATCH_MODE_MERGE};
+use crate::error::ServiceError;
+use crate::error::ServiceError::InvalidPatch;
+use crate::metadatas::FinalMetadata;
+use crate::storage_impl::get_storage;
+use crate::transaction::Transaction;
+
+#[derive(Deserialize)]
+struct SerdePatch {
+    #[serde(default)]
+    patch: String,
+}
+
+/**
+ * Patch mode selected in the transaction `mcdata`, empty when the data is a full document
+ */
+pub fn patch_mode(mcdata: &str) -> Result<String, ServiceError> {
+    let mode = serde_json::from_str::<SerdePatch>(mcdata)
+        .map(|sp| sp.patch)
+        .unwrap_or_default();
+
+    match mode.as_str() {
+        "" | PATCH_MODE_MERGE | PATCH_MODE_JSON_PATCH => Ok(mode),
+        _ => Err(InvalidPatch(format!("unknown patch mode: {}", mode))),
+    }
+}
+
+/**
+ * Checks that `patch` can be read as a patch of the given mode, without applying it
+ */
+pub fn validate_patch(mode: &str, patch: &str) -> Result<(), ServiceError> {
+    let patch_value: Value = serde_json::from_str(patch).map_err(|e| InvalidPatch(e.to_string()))?;
+
+    match mode {
+        PATCH_MODE_MERGE => Ok(()),
+        PATCH_MODE_JSON_PATCH => serde_json::from_value::<Patch>(patch_value)
+            .map(|_| ())
+            .map_err(|e| InvalidPatch(e.to_string())),
+        _ => Err(InvalidPatch(format!("unknown patch mode: {}", mode))),
+    }
+}
+
+/**
+ * Applies an RFC 7386 merge patch or an RFC 6902 JSON Patch to `document`
+ */
+pub fn apply_patch(mode: &str, mut document: Value, patch: &str) -> Result<String, ServiceError> {
+    validate_patch(mode, patch)?;
+
+    let patch_value: Value = serde_json::from_str(patch)?;
+
+    if mode == PATCH_MODE_MERGE {
+        json_patch::merge(&mut document, &patch_value);
+    } else {
+        let operations: Patch = serde_json::from_value(patch_value)?;
+        json_patch::patch(&mut document, &operations).map_err(|e| InvalidPatch(e.to_string()))?;
+    }
+
+    Ok(serde_json::to_string(&document)?)
+}
+
+/**
+ * Replaces the content of every final metadata with the result of applying it,
+ * as a patch, on top of the block content its record had before the transaction.
+ * Every bound meta contract patches the same base, so the patch lands once.
+ * Nothing is touched unless the transaction selected a patch mode
+ */
+pub fn patch_metadatas(
+    transaction: &Transaction,
+    metadatas: &mut [FinalMetadata],
+) -> Result<(), ServiceError> {
+    let mode = patch_mode(&transaction.mcdata)?;
+
+    if mode.is_empty() {
+        return Ok(());
+    }
+
+    let storage = get_storage();
+
+    for data in metadatas.iter_mut() {
+        let result = storage.get_owner_metadata(
+            transaction.data_key.clone(),
+            transaction.meta_contract_id.clone(),
+            data.public_key.clone(),
+            data.alias.clone(),
+            transaction.version.clone(),
+        );
+
+        let base_cid = match result {
+            Ok(metadata) => match storage.get_metadata_version_by_transaction(metadata.hash, transaction.hash.clone())? {
+                Some(version) => version.previous_cid,
+                None => metadata.cid,
+            },
+            Err(ServiceError::RecordNotFound(_)) => "".to_string(),
+            Err(e) => return Err(e),
+        };
+
+        let base = if base_cid.is_empty() {
+            json!({})
+        } else {
+            Block::fetch(base_cid)?.content
+        };
+
+        data.content = apply_patch(&mode, base, &data.content)?;
+    }
+
+    Ok(())
+}
+
+/**
+ * Fills what `content` leaves out with `defaults`, keys set in `content` always win
+ */
+pub fn inherit(mut defaults: Value, content: Value) -> Value {
+    if !defaults.is_object() || !content.is_object() {
+        return content;
+    }
+
+    json_patch::merge(&mut defaults, &content);
+    defaults
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_mode_without_loose() {
+        assert_eq!(patch_mode("{\"patch\":\"merge\"}").unwrap(), PATCH_MODE_MERGE);
+        assert_eq!(patch_mode("{\"loose\":0,\"patch\":\"json-patch\"}").unwrap(), PATCH_MODE_JSON_PATCH);
+        assert_eq!(patch_mode("{\"loose\":0}").unwrap(), "");
+        assert_eq!(patch_mode("").unwrap(), "");
+    }
+
+    #[test]
+    fn rejects_unknown_modes() {
+        assert!(patch_mode("{\"patch\":\"replace\"}").is_err());
+        assert!(validate_patch("replace", "{}").is_err());
+    }
+
+    #[test]
+    fn validates_patches_of_each_mode() {
+        assert!(validate_patch(PATCH_MODE_MERGE, "{\"a\":1}").is_ok());
+        assert!(validate_patch(PATCH_MODE_MERGE, "not json").is_err());
+        assert!(validate_patch(PATCH_MODE_JSON_PATCH, "[{\"op\":\"remove\",\"path\":\"/a\"}]").is_ok());
+        assert!(validate_patch(PATCH_MODE_JSON_PATCH, "{\"a\":1}").is_err());
+    }
+
+    #[test]
+    fn applies_a_merge_patch() {
+        let document = json!({ "name": "a", "attributes": { "color": "red", "size": 1 } });
+        let patched = apply_patch(PATCH_MODE_MERGE, document, "{\"attributes\":{\"color\":null,\"size\":2}}").unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<Value>(&patched).unwrap(),
+            json!({ "name": "a", "attributes": { "size": 2 } })
+        );
+    }
+
+    #[test]
+    fn applies_a_json_patch() {
+        let document = json!({ "name": "a", "tags": ["x"] });
+        let patch = "[{\"op\":\"replace\",\"path\":\"/name\",\"value\":\"b\"},{\"op\":\"add\",\"path\":\"/tags/-\",\"value\":\"y\"}]";
+        let patched = apply_patch(PATCH_MODE_JSON_PATCH, document, patch).unwrap();
+
+        assert_eq!(serde_json::from_str::<Value>(&patched).unwrap(), json!({ "name": "b", "tags": ["x", "y"] }));
+    }
+
+    #[test]
+    fn fails_a_json_patch_that_does_not_apply() {
+        let patch = "[{\"op\":\"remove\",\"path\":\"/missing\"}]";
+        assert!(apply_patch(PATCH_MODE_JSON_PATCH, json!({}), patch).is_err());
+    }
+
+    #[test]
+    fn inherits_missing_keys_only() {
+        let merged = inherit(json!({ "name": "collection", "image": "a" }), json!({ "name": "token" }));
+        assert_eq!(merged, json!({ "name": "token", "image": "a" }));
+
+        assert_eq!(inherit(json!({ "name": "collection" }), json!("text")), json!("text"));
+    }
+}
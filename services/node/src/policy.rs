@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use marine_rs_sdk::marine;
+use serde::Deserialize;
+
+use crate::defaults::{POLICY_MODE_ALLOWLIST, POLICY_MODE_DENYLIST};
+
+/**
+ * Who can publish metadata under a meta contract. `public_keys` is the allow or deny list of `mode`,
+ * `roles` maps an alias to the only keys that can write it. Both are stored as JSON
+ */
+#[marine]
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MetaContractPolicy {
+    pub meta_contract_id: String,
+    pub mode: String,
+    pub public_keys: String,
+    pub roles: String,
+}
+
+impl MetaContractPolicy {
+    pub fn key_list(&self) -> Vec<String> {
+        serde_json::from_str(&self.public_keys).unwrap_or_default()
+    }
+
+    pub fn role_map(&self) -> HashMap<String, Vec<String>> {
+        serde_json::from_str(&self.roles).unwrap_or_default()
+    }
+
+    /**
+     * Keys are compared case insensitively, hex keys are sent in either case
+     */
+    pub fn allows(&self, public_key: &str, alias: &str) -> bool {
+        let listed = |keys: &[String]| keys.iter().any(|key| key.eq_ignore_ascii_case(public_key));
+
+        let allowed = match self.mode.as_str() {
+            POLICY_MODE_ALLOWLIST => listed(&self.key_list()),
+            POLICY_MODE_DENYLIST => !listed(&self.key_list()),
+            _ => true,
+        };
+
+        match self.role_map().get(alias) {
+            Some(keys) => allowed && listed(keys),
+            None => allowed,
+        }
+    }
+}
+
+/**
+ * Policy sent in the `mcdata` of a contract transaction, it replaces the current one
+ */
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct SerdePolicy {
+    pub mode: String,
+    pub public_keys: Vec<String>,
+    pub roles: HashMap<String, Vec<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::defaults::POLICY_MODE_OPEN;
+
+    fn policy(mode: &str, public_keys: &str, roles: &str) -> MetaContractPolicy {
+        MetaContractPolicy {
+            meta_contract_id: "mc".to_string(),
+            mode: mode.to_string(),
+            public_keys: public_keys.to_string(),
+            roles: roles.to_string(),
+        }
+    }
+
+    #[test]
+    fn allowlist_only_lets_listed_keys_in() {
+        let policy = policy(POLICY_MODE_ALLOWLIST, "[\"0xABC\"]", "");
+
+        assert!(policy.allows("0xabc", "any"));
+        assert!(!policy.allows("0xdef", "any"));
+    }
+
+    #[test]
+    fn denylist_keeps_listed_keys_out() {
+        let policy = policy(POLICY_MODE_DENYLIST, "[\"0xabc\"]", "");
+
+        assert!(!policy.allows("0xABC", "any"));
+        assert!(policy.allows("0xdef", "any"));
+    }
+
+    #[test]
+    fn roles_restrict_their_alias_only() {
+        let policy = policy(POLICY_MODE_OPEN, "[]", "{\"admin\":[\"0xabc\"]}");
+
+        assert!(policy.allows("0xabc", "admin"));
+        assert!(!policy.allows("0xdef", "admin"));
+        assert!(policy.allows("0xdef", "profile"));
+    }
+
+    #[test]
+    fn roles_apply_on_top_of_the_list() {
+        let policy = policy(POLICY_MODE_DENYLIST, "[\"0xabc\"]", "{\"admin\":[\"0xabc\"]}");
+
+        assert!(!policy.allows("0xabc", "admin"));
+    }
+
+    #[test]
+    fn empty_lists() {
+        assert!(!policy(POLICY_MODE_ALLOWLIST, "[]", "").allows("0xabc", "any"));
+        assert!(policy(POLICY_MODE_DENYLIST, "", "").allows("0xabc", "any"));
+        assert!(!policy(POLICY_MODE_OPEN, "", "{\"admin\":[]}").allows("0xabc", "admin"));
+        assert!(policy(POLICY_MODE_OPEN, "", "").key_list().is_empty());
+        assert!(policy(POLICY_MODE_OPEN, "", "").role_map().is_empty());
+    }
+}
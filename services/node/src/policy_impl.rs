@@ -0,0 +1,73 @@
+use crate::defaults::META_CONTRACT_POLICIES_TABLE_NAME;
+use crate::error::ServiceError;
+use crate::policy::{MetaContractPolicy, SerdePolicy};
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+
+impl Storage {
+    pub fn create_meta_contract_policies_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                meta_contract_id TEXT PRIMARY KEY UNIQUE,
+                mode TEXT not null,
+                public_keys TEXT not null,
+                roles TEXT not null
+            )",
+            META_CONTRACT_POLICIES_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_meta_contract_policies_table error: {}", error);
+        }
+    }
+
+    pub fn set_meta_contract_policy(&self, meta_contract_id: String, policy: SerdePolicy) -> Result<(), ServiceError> {
+        let s = format!(
+            "insert or replace into {} (meta_contract_id, mode, public_keys, roles) values ('{}', '{}', '{}', '{}')",
+            META_CONTRACT_POLICIES_TABLE_NAME,
+            meta_contract_id,
+            policy.mode,
+            Storage::escape(&serde_json::to_string(&policy.public_keys)?),
+            Storage::escape(&serde_json::to_string(&policy.roles)?),
+        );
+
+        Storage::execute(s)?;
+
+        Ok(())
+    }
+
+    /**
+     * Policy of a meta contract, none when anyone can publish
+     */
+    pub fn get_meta_contract_policy(&self, meta_contract_id: String) -> Result<Option<MetaContractPolicy>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE meta_contract_id = '{}'",
+            META_CONTRACT_POLICIES_TABLE_NAME, meta_contract_id
+        );
+
+        let result = Storage::read(statement)?;
+        Ok(read(result)?.first().cloned())
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<MetaContractPolicy>, ServiceError> {
+    let mut policies = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::MetaContractPolicy(policy) => policies.push(policy),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        META_CONTRACT_POLICIES_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(policies)
+}
@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+/**
+ * First key to register a meta contract. It alone can suspend it or change its settings
+ */
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MetaContractRegistration {
+    pub meta_contract_id: String,
+    pub public_key: String,
+    pub transaction_hash: String,
+    pub registered_at: u64,
+}
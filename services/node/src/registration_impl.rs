@@ -0,0 +1,97 @@
+use crate::defaults::{META_CONTRACT_REGISTRATIONS_TABLE_NAME, META_CONTRACT_TABLE_NAME, NATIVE_META_CONTRACT_PREFIX};
+use crate::error::ServiceError;
+use crate::registration::MetaContractRegistration;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+
+impl Storage {
+    /**
+     * Contracts registered before this table existed are owned by the key of their oldest `metacontracts` row.
+ * Reserved native ids are never registered
+     */
+    pub fn create_meta_contract_registrations_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                meta_contract_id TEXT PRIMARY KEY UNIQUE,
+                public_key TEXT not null,
+                transaction_hash TEXT not null,
+                registered_at INTEGER not null
+            )",
+            META_CONTRACT_REGISTRATIONS_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_meta_contract_registrations_table error: {}", error);
+        }
+
+        let backfill = format!(
+            "insert or ignore into {} (meta_contract_id, public_key, transaction_hash, registered_at)
+            SELECT meta_contract_id, public_key, '', 0 FROM {} WHERE meta_contract_id != '' AND meta_contract_id NOT LIKE '{}%' ORDER BY rowid ASC",
+            META_CONTRACT_REGISTRATIONS_TABLE_NAME, META_CONTRACT_TABLE_NAME, NATIVE_META_CONTRACT_PREFIX
+        );
+
+        if let Err(error) = Storage::execute(backfill) {
+            println!("create_meta_contract_registrations_table backfill error: {}", error);
+        }
+    }
+
+    /**
+     * Records the registrant of a meta contract. Later registrations of the same id are ignored
+     */
+    pub fn register_meta_contract(&self, registration: MetaContractRegistration) -> Result<(), ServiceError> {
+        let s = format!(
+            "insert or ignore into {} (meta_contract_id, public_key, transaction_hash, registered_at) values ('{}', '{}', '{}', '{}')",
+            META_CONTRACT_REGISTRATIONS_TABLE_NAME,
+            registration.meta_contract_id,
+            registration.public_key,
+            registration.transaction_hash,
+            registration.registered_at,
+        );
+
+        Storage::execute(s)?;
+
+        Ok(())
+    }
+
+    pub fn get_meta_contract_registration(
+        &self,
+        meta_contract_id: String,
+    ) -> Result<Option<MetaContractRegistration>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE meta_contract_id = '{}'",
+            META_CONTRACT_REGISTRATIONS_TABLE_NAME, meta_contract_id
+        );
+
+        let result = Storage::read(statement)?;
+        Ok(read(result)?.first().cloned())
+    }
+
+    pub fn is_meta_contract_registrant(&self, meta_contract_id: String, public_key: &str) -> Result<bool, ServiceError> {
+        Ok(self
+            .get_meta_contract_registration(meta_contract_id)?
+            .map(|registration| registration.public_key == public_key)
+            .unwrap_or(false))
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<MetaContractRegistration>, ServiceError> {
+    let mut registrations = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::MetaContractRegistration(registration) => registrations.push(registration),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        META_CONTRACT_REGISTRATIONS_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(registrations)
+}
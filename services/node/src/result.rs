@@ -2,12 +2,25 @@ use eyre::Result;
 use marine_rs_sdk::marine;
 
 use crate::{
+    asset::Asset,
+    clone_edge::CloneEdge,
     cron::{Cron, CronResult},
     cron_tx::CronTx,
+    diff::MetadataDiff,
     error::ServiceError,
+    freeze::MetadataFreeze,
+    grant::Grant,
     meta_contract::MetaContract,
-    metadatas::Metadata,
-    transaction::Transaction,
+    meta_contract_bindings::MetaContractBinding,
+    meta_contract_versions::MetaContractVersion,
+    metadata_fts::MetadataTextMatch,
+    metadata_versions::MetadataVersion,
+    metadatas::{Metadata, MetadataContent},
+    normalize::MigrationReport,
+    transaction::{Transaction, TransactionReceipt},
+    policy::MetaContractPolicy,
+    suspension::MetaContractSuspension,
+    transfer::OwnershipTransfer,
 };
 
 #[marine]
@@ -78,20 +91,26 @@ pub struct FdbMetadataResult {
     pub success: bool,
     pub err_msg: String,
     pub metadata: Metadata,
+    pub frozen: bool,
+    pub freeze_transaction_hash: String,
 }
 
-impl From<Result<Metadata, ServiceError>> for FdbMetadataResult {
-    fn from(result: Result<Metadata, ServiceError>) -> Self {
+impl From<Result<(Metadata, Option<MetadataFreeze>), ServiceError>> for FdbMetadataResult {
+    fn from(result: Result<(Metadata, Option<MetadataFreeze>), ServiceError>) -> Self {
         match result {
-            Ok(metadata) => Self {
+            Ok((metadata, freeze)) => Self {
                 success: true,
                 err_msg: "".to_string(),
                 metadata,
+                frozen: freeze.is_some(),
+                freeze_transaction_hash: freeze.map(|f| f.transaction_hash).unwrap_or_default(),
             },
             Err(err) => Self {
                 success: false,
                 err_msg: err.to_string(),
                 metadata: Metadata::default(),
+                frozen: false,
+                freeze_transaction_hash: "".to_string(),
             },
         }
     }
@@ -150,6 +169,56 @@ impl From<Result<Vec<String>, ServiceError>> for FdbMetadataHistoryResult {
     }
 }
 
+#[marine]
+#[derive(Debug)]
+pub struct FdbMetadataVersionsResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub versions: Vec<MetadataVersion>,
+}
+
+impl From<Result<Vec<MetadataVersion>, ServiceError>> for FdbMetadataVersionsResult {
+    fn from(result: Result<Vec<MetadataVersion>, ServiceError>) -> Self {
+        match result {
+            Ok(versions) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                versions,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                versions: Vec::new(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbMetadataVersionResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub version: MetadataVersion,
+}
+
+impl From<Result<MetadataVersion, ServiceError>> for FdbMetadataVersionResult {
+    fn from(result: Result<MetadataVersion, ServiceError>) -> Self {
+        match result {
+            Ok(version) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                version,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                version: MetadataVersion::default(),
+            },
+        }
+    }
+}
+
 #[marine]
 #[derive(Debug)]
 pub struct FdbMetaContractResult {
@@ -200,6 +269,62 @@ impl From<Result<Vec<Cron>, ServiceError>> for FdbCronsResult {
     }
 }
 
+#[marine]
+#[derive(Debug)]
+pub struct FdbCronResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub cron: Cron,
+}
+
+impl From<Result<Cron, ServiceError>> for FdbCronResult {
+    fn from(result: Result<Cron, ServiceError>) -> Self {
+        match result {
+            Ok(cron) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                cron,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                cron: Cron::default(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbCronRangeResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub has_range: bool,
+    pub from_block: u64,
+    pub to_block: u64,
+}
+
+impl From<Result<Option<(u64, u64)>, ServiceError>> for FdbCronRangeResult {
+    fn from(result: Result<Option<(u64, u64)>, ServiceError>) -> Self {
+        match result {
+            Ok(range) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                has_range: range.is_some(),
+                from_block: range.map(|(from, _)| from).unwrap_or_default(),
+                to_block: range.map(|(_, to)| to).unwrap_or_default(),
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                has_range: false,
+                from_block: 0,
+                to_block: 0,
+            },
+        }
+    }
+}
+
 #[marine]
 #[derive(Debug)]
 pub struct FdbCronTxsResult {
@@ -249,3 +374,387 @@ impl From<Result<CronTx, ServiceError>> for FdbCronTxResult {
         }
     }
 }
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbMetadataDiffResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub diff: MetadataDiff,
+}
+
+impl From<Result<MetadataDiff, ServiceError>> for FdbMetadataDiffResult {
+    fn from(result: Result<MetadataDiff, ServiceError>) -> Self {
+        match result {
+            Ok(diff) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                diff,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                diff: MetadataDiff::default(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbMetadataTextSearchResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub results: Vec<MetadataTextMatch>,
+    pub next_cursor: String,
+}
+
+impl From<Result<(Vec<MetadataTextMatch>, String), ServiceError>> for FdbMetadataTextSearchResult {
+    fn from(result: Result<(Vec<MetadataTextMatch>, String), ServiceError>) -> Self {
+        match result {
+            Ok((results, next_cursor)) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                results,
+                next_cursor,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                results: Vec::new(),
+                next_cursor: "".to_string(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbOwnershipTransfersResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub transfers: Vec<OwnershipTransfer>,
+}
+
+impl From<Result<Vec<OwnershipTransfer>, ServiceError>> for FdbOwnershipTransfersResult {
+    fn from(result: Result<Vec<OwnershipTransfer>, ServiceError>) -> Self {
+        match result {
+            Ok(transfers) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                transfers,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                transfers: Vec::new(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbGrantsResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub grants: Vec<Grant>,
+}
+
+impl From<Result<Vec<Grant>, ServiceError>> for FdbGrantsResult {
+    fn from(result: Result<Vec<Grant>, ServiceError>) -> Self {
+        match result {
+            Ok(grants) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                grants,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                grants: Vec::new(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbMetadataContentResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub content: MetadataContent,
+}
+
+impl From<Result<MetadataContent, ServiceError>> for FdbMetadataContentResult {
+    fn from(result: Result<MetadataContent, ServiceError>) -> Self {
+        match result {
+            Ok(content) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                content,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                content: MetadataContent::default(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbTokenMetadataResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub metadata: String,
+}
+
+impl From<Result<String, ServiceError>> for FdbTokenMetadataResult {
+    fn from(result: Result<String, ServiceError>) -> Self {
+        match result {
+            Ok(metadata) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                metadata,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                metadata: "".to_string(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbMigrationResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub report: MigrationReport,
+}
+
+impl From<Result<MigrationReport, ServiceError>> for FdbMigrationResult {
+    fn from(result: Result<MigrationReport, ServiceError>) -> Self {
+        match result {
+            Ok(report) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                report,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                report: MigrationReport::default(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbAssetResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub asset: Asset,
+}
+
+impl From<Result<Asset, ServiceError>> for FdbAssetResult {
+    fn from(result: Result<Asset, ServiceError>) -> Self {
+        match result {
+            Ok(asset) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                asset,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                asset: Asset::default(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbMetaContractVersionsResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub versions: Vec<MetaContractVersion>,
+}
+
+impl From<Result<Vec<MetaContractVersion>, ServiceError>> for FdbMetaContractVersionsResult {
+    fn from(result: Result<Vec<MetaContractVersion>, ServiceError>) -> Self {
+        match result {
+            Ok(versions) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                versions,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                versions: Vec::new(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbMetaContractsResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub metas: Vec<MetaContract>,
+}
+
+impl From<Result<Vec<MetaContract>, ServiceError>> for FdbMetaContractsResult {
+    fn from(result: Result<Vec<MetaContract>, ServiceError>) -> Self {
+        match result {
+            Ok(metas) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                metas,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                metas: Vec::new(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbMetaContractBindingsResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub bindings: Vec<MetaContractBinding>,
+}
+
+impl From<Result<Vec<MetaContractBinding>, ServiceError>> for FdbMetaContractBindingsResult {
+    fn from(result: Result<Vec<MetaContractBinding>, ServiceError>) -> Self {
+        match result {
+            Ok(bindings) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                bindings,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                bindings: Vec::new(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbMetaContractSuspensionResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub suspended: bool,
+    pub suspension: MetaContractSuspension,
+}
+
+impl From<Result<Option<MetaContractSuspension>, ServiceError>> for FdbMetaContractSuspensionResult {
+    fn from(result: Result<Option<MetaContractSuspension>, ServiceError>) -> Self {
+        match result {
+            Ok(suspension) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                suspended: suspension.is_some(),
+                suspension: suspension.unwrap_or_default(),
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                suspended: false,
+                suspension: MetaContractSuspension::default(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbMetaContractPolicyResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub restricted: bool,
+    pub policy: MetaContractPolicy,
+}
+
+impl From<Result<Option<MetaContractPolicy>, ServiceError>> for FdbMetaContractPolicyResult {
+    fn from(result: Result<Option<MetaContractPolicy>, ServiceError>) -> Self {
+        match result {
+            Ok(policy) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                restricted: policy.is_some(),
+                policy: policy.unwrap_or_default(),
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                restricted: false,
+                policy: MetaContractPolicy::default(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbCloneEdgesResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub edges: Vec<CloneEdge>,
+}
+
+impl From<Result<Vec<CloneEdge>, ServiceError>> for FdbCloneEdgesResult {
+    fn from(result: Result<Vec<CloneEdge>, ServiceError>) -> Self {
+        match result {
+            Ok(edges) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                edges,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                edges: Vec::new(),
+            },
+        }
+    }
+}
+
+#[marine]
+#[derive(Debug)]
+pub struct FdbTransactionReceiptsResult {
+    pub success: bool,
+    pub err_msg: String,
+    pub receipts: Vec<TransactionReceipt>,
+}
+
+impl From<Result<Vec<TransactionReceipt>, ServiceError>> for FdbTransactionReceiptsResult {
+    fn from(result: Result<Vec<TransactionReceipt>, ServiceError>) -> Self {
+        match result {
+            Ok(receipts) => Self {
+                success: true,
+                err_msg: "".to_string(),
+                receipts,
+            },
+            Err(err) => Self {
+                success: false,
+                err_msg: err.to_string(),
+                receipts: Vec::new(),
+            },
+        }
+    }
+}
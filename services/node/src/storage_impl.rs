@@ -1,11 +1,26 @@
 use std::collections::HashMap;
 
+use crate::clone_edge::CloneEdge;
 use crate::cron::Cron;
 use crate::cron_tx::CronTx;
 use crate::curl;
 use crate::defaults::{SQL_EXECUTE, SQL_QUERY};
 use crate::error::ServiceError;
 use crate::meta_contract::MetaContract;
+use crate::freeze::MetadataFreeze;
+use crate::grant::Grant;
+use crate::asset::Asset;
+use crate::meta_contract_bindings::MetaContractBinding;
+use crate::meta_contract_versions::MetaContractVersion;
+use crate::normalize::KeyCoordinates;
+use crate::policy::MetaContractPolicy;
+use crate::registration::MetaContractRegistration;
+use crate::suspension::MetaContractSuspension;
+use crate::token_metadata::MetadataProfile;
+use crate::transfer::OwnershipTransfer;
+use crate::metadata_fts::MetadataTextHit;
+use crate::metadata_index::MetadataIndexPath;
+use crate::metadata_versions::MetadataVersion;
 use crate::metadatas::Metadata;
 use crate::transaction::{Transaction, TransactionReceipt};
 use eyre::Result;
@@ -33,11 +48,27 @@ pub struct RQLiteResult {
 #[serde(untagged)]
 pub enum Row {
     Metadata(Metadata),
+    // version and binding rows also have every field of a meta contract row
+    MetaContractVersion(MetaContractVersion),
+    MetaContractBinding(MetaContractBinding),
     MetaContract(MetaContract),
     Transaction(Transaction),
     TransactionReceipt(TransactionReceipt),
     Cron(Cron),
     CronTx(CronTx),
+    MetadataVersion(MetadataVersion),
+    MetadataIndexPath(MetadataIndexPath),
+    MetadataTextHit(MetadataTextHit),
+    MetadataFreeze(MetadataFreeze),
+    OwnershipTransfer(OwnershipTransfer),
+    Grant(Grant),
+    MetadataProfile(MetadataProfile),
+    KeyCoordinates(KeyCoordinates),
+    Asset(Asset),
+    MetaContractSuspension(MetaContractSuspension),
+    MetaContractPolicy(MetaContractPolicy),
+    CloneEdge(CloneEdge),
+    MetaContractRegistration(MetaContractRegistration),
 }
 
 #[inline]
@@ -97,7 +128,28 @@ impl Storage {
         Err(ServiceError::InternalError("Invalid response".to_string()))
     }
 
+    /**
+     * Brings a table created by an older release up to the current schema. Adding a column
+     * that already exists is a no-op, so it runs on every init
+     */
+    pub fn add_column(table: &str, column: &str, definition: &str) {
+        let statement = format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition);
+
+        if let Err(error) = Storage::execute(statement) {
+            if !error.to_string().contains("duplicate column name") {
+                println!("add_column {}.{} error: {}", table, column, error);
+            }
+        }
+    }
+
     pub fn trimmer(input: String) -> String {
       input[1..input.len()-1].to_string()
     }
+
+    /**
+     * Makes free text safe to embed in a quoted SQL literal of the request body
+     */
+    pub fn escape(input: &str) -> String {
+      Storage::trimmer(serde_json::to_string(&input.replace('\'', "''")).unwrap())
+    }
 }
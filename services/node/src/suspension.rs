@@ -0,0 +1,22 @@
+use marine_rs_sdk::marine;
+use serde::Deserialize;
+
+/**
+ * Emergency stop placed on a meta contract by its owner
+ */
+#[marine]
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MetaContractSuspension {
+    pub meta_contract_id: String,
+    pub public_key: String,
+    pub transaction_hash: String,
+    pub timestamp: u64,
+}
+
+/**
+ * Data of a "suspend" transaction, `suspended: false` lifts the suspension
+ */
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct SerdeSuspend {
+    pub suspended: bool,
+}
@@ -0,0 +1,84 @@
+use crate::defaults::META_CONTRACT_SUSPENSIONS_TABLE_NAME;
+use crate::error::ServiceError;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+use crate::suspension::MetaContractSuspension;
+
+impl Storage {
+    pub fn create_meta_contract_suspensions_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                meta_contract_id TEXT PRIMARY KEY UNIQUE,
+                public_key TEXT not null,
+                transaction_hash TEXT not null,
+                timestamp INTEGER not null
+            )",
+            META_CONTRACT_SUSPENSIONS_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_meta_contract_suspensions_table error: {}", error);
+        }
+    }
+
+    pub fn write_meta_contract_suspension(&self, suspension: MetaContractSuspension) -> Result<(), ServiceError> {
+        let s = format!(
+            "insert or replace into {} (meta_contract_id, public_key, transaction_hash, timestamp) values ('{}', '{}', '{}', '{}')",
+            META_CONTRACT_SUSPENSIONS_TABLE_NAME,
+            suspension.meta_contract_id,
+            suspension.public_key,
+            suspension.transaction_hash,
+            suspension.timestamp,
+        );
+
+        Storage::execute(s)?;
+
+        Ok(())
+    }
+
+    pub fn delete_meta_contract_suspension(&self, meta_contract_id: String) -> Result<(), ServiceError> {
+        let s = format!(
+            "DELETE FROM {} WHERE meta_contract_id = '{}'",
+            META_CONTRACT_SUSPENSIONS_TABLE_NAME, meta_contract_id
+        );
+
+        Storage::execute(s)?;
+
+        Ok(())
+    }
+
+    pub fn get_meta_contract_suspension(
+        &self,
+        meta_contract_id: String,
+    ) -> Result<Option<MetaContractSuspension>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE meta_contract_id = '{}'",
+            META_CONTRACT_SUSPENSIONS_TABLE_NAME, meta_contract_id
+        );
+
+        let result = Storage::read(statement)?;
+        Ok(read(result)?.first().cloned())
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<MetaContractSuspension>, ServiceError> {
+    let mut suspensions = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::MetaContractSuspension(suspension) => suspensions.push(suspension),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        META_CONTRACT_SUSPENSIONS_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(suspensions)
+}
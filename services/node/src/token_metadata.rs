@@ -0,0 +1,123 @@
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+/**
+ * Aliases merged for a profile of a meta contract, highest priority first.
+ * `aliases` is stored as a JSON array
+ */
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MetadataProfile {
+    pub meta_contract_id: String,
+    pub name: String,
+    pub aliases: String,
+}
+
+impl MetadataProfile {
+    pub fn alias_list(&self) -> Vec<String> {
+        serde_json::from_str(&self.aliases).unwrap_or_default()
+    }
+}
+
+/**
+ * Shapes merged block contents as an ERC-721 / ERC-1155 metadata document,
+ * as read by OpenSea. Unknown fields are kept
+ */
+pub fn render(merged: Value) -> Value {
+    let mut document = match merged {
+        Value::Object(fields) => fields,
+        _ => Map::new(),
+    };
+
+    if !document.contains_key("image") {
+        let image = document
+            .get("image_url")
+            .cloned()
+            .unwrap_or_else(|| Value::String("".to_string()));
+        document.insert("image".to_string(), image);
+    }
+
+    // a flat object of traits is accepted and turned into the attributes array
+    let attributes = match document.remove("attributes") {
+        Some(Value::Array(attributes)) => Value::Array(attributes),
+        Some(Value::Object(traits)) => Value::Array(
+            traits
+                .into_iter()
+                .map(|(trait_type, value)| json!({ "trait_type": trait_type, "value": value }))
+                .collect(),
+        ),
+        _ => json!([]),
+    };
+    document.insert("attributes".to_string(), attributes);
+
+    if !matches!(document.get("properties"), Some(Value::Object(_))) {
+        document.insert("properties".to_string(), json!({}));
+    }
+
+    Value::Object(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_the_standard_fields() {
+        let document = render(json!({ "name": "Token #1" }));
+
+        assert_eq!(
+            document,
+            json!({ "name": "Token #1", "image": "", "attributes": [], "properties": {} })
+        );
+    }
+
+    #[test]
+    fn falls_back_to_image_url() {
+        let document = render(json!({ "image_url": "ipfs://a" }));
+        assert_eq!(document["image"], json!("ipfs://a"));
+        assert_eq!(document["image_url"], json!("ipfs://a"));
+
+        let document = render(json!({ "image": "ipfs://b", "image_url": "ipfs://a" }));
+        assert_eq!(document["image"], json!("ipfs://b"));
+    }
+
+    #[test]
+    fn turns_flat_traits_into_attributes() {
+        let document = render(json!({ "attributes": { "color": "red", "level": 3 } }));
+
+        assert_eq!(
+            document["attributes"],
+            json!([{ "trait_type": "color", "value": "red" }, { "trait_type": "level", "value": 3 }])
+        );
+    }
+
+    #[test]
+    fn keeps_attribute_arrays_and_unknown_fields() {
+        let attributes = json!([{ "trait_type": "color", "value": "red" }]);
+        let document = render(json!({ "attributes": attributes.clone(), "extra": true, "properties": { "a": 1 } }));
+
+        assert_eq!(document["attributes"], attributes);
+        assert_eq!(document["extra"], json!(true));
+        assert_eq!(document["properties"], json!({ "a": 1 }));
+    }
+
+    #[test]
+    fn replaces_invalid_shapes() {
+        let document = render(json!({ "attributes": "red", "properties": [] }));
+        assert_eq!(document["attributes"], json!([]));
+        assert_eq!(document["properties"], json!({}));
+
+        assert_eq!(render(json!("text"))["attributes"], json!([]));
+    }
+
+    #[test]
+    fn reads_the_stored_alias_list() {
+        let profile = MetadataProfile {
+            aliases: "[\"main\",\"fallback\"]".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(profile.alias_list(), vec!["main", "fallback"]);
+
+        let profile = MetadataProfile { aliases: "oops".to_string(), ..Default::default() };
+        assert!(profile.alias_list().is_empty());
+    }
+}
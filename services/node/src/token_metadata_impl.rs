@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use crate::defaults::METADATA_PROFILES_TABLE_NAME;
+use crate::error::ServiceError;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+use crate::token_metadata::MetadataProfile;
+
+impl Storage {
+    pub fn create_metadata_profiles_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                meta_contract_id TEXT not null,
+                name TEXT not null,
+                aliases TEXT not null,
+                PRIMARY KEY (meta_contract_id, name)
+            )",
+            METADATA_PROFILES_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_metadata_profiles_table error: {}", error);
+        }
+    }
+
+    /**
+     * Replaces the rendering profiles of a meta contract
+     */
+    pub fn set_metadata_profiles(
+        &self,
+        meta_contract_id: String,
+        profiles: HashMap<String, Vec<String>>,
+    ) -> Result<(), ServiceError> {
+        Storage::execute(format!(
+            "DELETE FROM {} WHERE meta_contract_id = '{}'",
+            METADATA_PROFILES_TABLE_NAME, meta_contract_id
+        ))?;
+
+        for (name, aliases) in profiles {
+            Storage::execute(format!(
+                "insert into {} (meta_contract_id, name, aliases) values ('{}', '{}', '{}')",
+                METADATA_PROFILES_TABLE_NAME,
+                meta_contract_id,
+                Storage::escape(&name),
+                Storage::escape(&serde_json::to_string(&aliases)?),
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_metadata_profile(&self, meta_contract_id: String, name: String) -> Result<MetadataProfile, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE meta_contract_id = '{}' AND name = '{}'",
+            METADATA_PROFILES_TABLE_NAME,
+            meta_contract_id,
+            Storage::escape(&name)
+        );
+
+        let result = Storage::read(statement)?;
+        match read(result) {
+            Ok(profiles) => profiles
+                .first()
+                .cloned()
+                .ok_or_else(|| ServiceError::RecordNotFound(format!("No profile {}", name))),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<MetadataProfile>, ServiceError> {
+    let mut profiles = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::MetadataProfile(profile) => profiles.push(profile),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        METADATA_PROFILES_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(profiles)
+}
@@ -21,6 +21,8 @@ pub struct Transaction {
     pub version: String,
     pub status: i64,
     pub mcdata: String,
+    pub expected_cid: String,
+    pub grant_hash: String,
 }
 
 #[marine]
@@ -37,6 +39,9 @@ pub struct TransactionRequest {
   pub token_id: String,
   pub version: String,
   pub mcdata: String,
+  pub expected_cid: String,
+  pub grant_hash: String,
+  pub asset_id: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -72,6 +77,7 @@ pub struct TransactionReceipt {
     pub timestamp: u64,
     pub error_text: String,
     pub data: String,
+    pub meta_contract_version: i64,
 }
 
 impl Transaction {
@@ -91,6 +97,8 @@ impl Transaction {
         mcdata: String,
         status: i64,
         previous_data: String,
+        expected_cid: String,
+        grant_hash: String,
     ) -> Self {
       let hash = Self::generate_hash(
           meta_contract_id.clone(),
@@ -121,6 +129,8 @@ impl Transaction {
           version,
           status,
           mcdata,
+          expected_cid,
+          grant_hash,
       }
     }
 
@@ -1,5 +1,6 @@
 use crate::storage_impl::{RQLiteResult, Row, Storage};
 use crate::transaction::TransactionReceipt;
+use crate::defaults::{CONTRACT_RECEIPTS_TABLE_NAME, META_CONTRACT_VERSIONS_TABLE_NAME, RECEIPT_STATUS_SUCCESS, TRANSACTIONS_TABLE_NAME};
 use crate::{defaults::TRANSACTION_RECEIPT_TABLE_NAME, error::ServiceError};
 
 impl Storage {
@@ -12,33 +13,96 @@ impl Storage {
           status INTEGER not null,
           timestamp INTEGER not null,
           error_text INTEGER not null,
-          data text null
+          data text null,
+          meta_contract_version INTEGER not null default 0
           )",
             TRANSACTION_RECEIPT_TABLE_NAME
         );
 
         Storage::execute(table_schema);
+
+        Storage::add_column(TRANSACTION_RECEIPT_TABLE_NAME, "meta_contract_version", "INTEGER not null default 0");
+
+        // every meta contract that runs a transaction keeps its own receipt
+        let table_schema = format!(
+            "
+          CREATE TABLE IF NOT EXISTS {} (
+          hash varchar(32) not null,
+          meta_contract_id varchar(32) not null,
+          status INTEGER not null,
+          timestamp INTEGER not null,
+          error_text TEXT not null,
+          data text null,
+          meta_contract_version INTEGER not null default 0,
+          PRIMARY KEY (hash, meta_contract_id, meta_contract_version)
+          )",
+            CONTRACT_RECEIPTS_TABLE_NAME
+        );
+
+        if let Err(error) = Storage::execute(table_schema) {
+            println!("create_transaction_receipt_table error: {}", error);
+        }
     }
 
+    /**
+     * Records the receipt of the meta contract that ran the transaction. Without an explicit contract
+     * version, the binding of that meta contract active for the transaction's token_key is recorded.
+     * The receipt of the transaction itself is the failed one of its contracts if any, the latest one otherwise
+     */
     pub fn write_transaction_receipt(
         &self,
         receipt: TransactionReceipt,
     ) -> Result<String, ServiceError> {
+        let meta_contract_version = if receipt.meta_contract_version > 0 {
+            receipt.meta_contract_version.to_string()
+        } else {
+            format!(
+                "COALESCE((SELECT MAX(version) FROM {} WHERE meta_contract_id = '{}' AND token_key = (SELECT token_key FROM {} WHERE hash = '{}')), 0)",
+                META_CONTRACT_VERSIONS_TABLE_NAME, receipt.meta_contract_id, TRANSACTIONS_TABLE_NAME, receipt.hash
+            )
+        };
+
         let s = format!(
-          "insert or replace into {} (hash, meta_contract_id, status, timestamp, error_text, data) values ('{}', '{}', '{}', '{}', '{}', '{}')",
-          TRANSACTION_RECEIPT_TABLE_NAME,
+          "insert or replace into {} (hash, meta_contract_id, status, timestamp, error_text, data, meta_contract_version) values ('{}', '{}', '{}', '{}', '{}', '{}', {})",
+          CONTRACT_RECEIPTS_TABLE_NAME,
           receipt.hash,
           receipt.meta_contract_id,
           receipt.status,
           receipt.timestamp,
-          receipt.error_text,
-          receipt.data
+          Storage::escape(&receipt.error_text),
+          Storage::escape(&receipt.data),
+          meta_contract_version
+      );
+
+        Storage::execute(s)?;
+
+        let s = format!(
+          "insert or replace into {} (hash, meta_contract_id, status, timestamp, error_text, data, meta_contract_version)
+          SELECT hash, meta_contract_id, status, timestamp, error_text, data, meta_contract_version FROM {}
+          WHERE hash = '{}' ORDER BY status = {} ASC, timestamp DESC, rowid DESC LIMIT 1",
+          TRANSACTION_RECEIPT_TABLE_NAME,
+          CONTRACT_RECEIPTS_TABLE_NAME,
+          receipt.hash,
+          RECEIPT_STATUS_SUCCESS,
       );
 
-        let result = Storage::execute(s);
+        Storage::execute(s)?;
         Ok(receipt.hash)
     }
 
+    /**
+     * Receipts of every meta contract that ran a transaction
+     */
+    pub fn get_contract_receipts(&self, hash: String) -> Result<Vec<TransactionReceipt>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE hash = '{}' ORDER BY timestamp ASC",
+            CONTRACT_RECEIPTS_TABLE_NAME, hash
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+
     pub fn get_transaction_receipt(
         &self,
         hash: String,
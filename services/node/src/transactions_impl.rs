@@ -24,7 +24,9 @@ impl Storage {
                 token_id TEXT,
                 version varchar(32) NOT NULL,
                 mcdata TEXT NULL,
-                status INTEGER NOT NULL
+                status INTEGER NOT NULL,
+                expected_cid TEXT not null default '',
+                grant_hash TEXT not null default ''
             )",
             TRANSACTIONS_TABLE_NAME
         );
@@ -34,12 +36,24 @@ impl Storage {
         if let Err(error) = result {
             println!("create_transactions_table error: {}", error);
         }
+
+        Storage::add_column(TRANSACTIONS_TABLE_NAME, "expected_cid", "TEXT not null default ''");
+        Storage::add_column(TRANSACTIONS_TABLE_NAME, "grant_hash", "TEXT not null default ''");
+
+        // nodes that already added both columns as nullable keep rows that cannot be read back
+        for column in ["expected_cid", "grant_hash"] {
+            let statement = format!("update {} set {} = '' where {} IS NULL", TRANSACTIONS_TABLE_NAME, column, column);
+
+            if let Err(error) = Storage::execute(statement) {
+                println!("create_transactions_table {} error: {}", column, error);
+            }
+        }
     }
 
     pub fn write_transaction(&self, transaction: Transaction) -> Result<String, ServiceError> {
         let s = format!(
-            "insert into {} (hash, method, meta_contract_id, token_key, data_key, data, public_key, alias, timestamp, chain_id, token_address, token_id, version, mcdata, status) 
-            values ('{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}')",
+            "insert into {} (hash, method, meta_contract_id, token_key, data_key, data, public_key, alias, timestamp, chain_id, token_address, token_id, version, mcdata, status, expected_cid, grant_hash) 
+            values ('{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}')",
             TRANSACTIONS_TABLE_NAME,
             transaction.hash,
             transaction.method,
@@ -56,6 +70,8 @@ impl Storage {
             transaction.version,
             Storage::trimmer(serde_json::to_string(&transaction.mcdata).unwrap()),
             transaction.status,
+            transaction.expected_cid,
+            transaction.grant_hash,
         );
 
         let result = Storage::execute(s);
@@ -0,0 +1,32 @@
+use marine_rs_sdk::marine;
+use serde::Deserialize;
+
+/**
+ * Change of owner of a metadata record or of a token_key's meta contract binding.
+ * `subject` is the metadata hash or the token_key
+ */
+#[marine]
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct OwnershipTransfer {
+    pub transaction_hash: String,
+    pub scope: String,
+    pub subject: String,
+    pub from_public_key: String,
+    pub to_public_key: String,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct SerdeTransfer {
+    pub scope: String,
+    pub new_public_key: String,
+}
+
+/**
+ * Recipient consent carried in the `mcdata` of a transfer transaction,
+ * a signature of the transaction data by the new owner
+ */
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct SerdeTransferConsent {
+    pub recipient_signature: String,
+}
@@ -0,0 +1,140 @@
+use crate::defaults::{
+    META_CONTRACT_BINDINGS_TABLE_NAME, META_CONTRACT_REGISTRATIONS_TABLE_NAME, META_CONTRACT_TABLE_NAME, METADATAS_TABLE_NAME,
+    OWNERSHIP_TRANSFERS_TABLE_NAME,
+};
+use crate::error::ServiceError;
+use crate::error::ServiceError::InternalError;
+use crate::meta_contract::MetaContract;
+use crate::metadatas::Metadata;
+use crate::storage_impl::{RQLiteResult, Row, Storage};
+use crate::transfer::OwnershipTransfer;
+
+impl Storage {
+    pub fn create_ownership_transfers_table(&self) {
+        let table_schema = format!(
+            "
+            CREATE TABLE IF NOT EXISTS {} (
+                transaction_hash TEXT not null primary key,
+                scope TEXT not null,
+                subject TEXT not null,
+                from_public_key TEXT not null,
+                to_public_key TEXT not null,
+                timestamp INTEGER not null
+            )",
+            OWNERSHIP_TRANSFERS_TABLE_NAME
+        );
+
+        let result = Storage::execute(table_schema);
+
+        if let Err(error) = result {
+            println!("create_ownership_transfers_table error: {}", error);
+        }
+    }
+
+    pub fn write_ownership_transfer(&self, transfer: OwnershipTransfer) -> Result<(), ServiceError> {
+        let s = format!(
+            "insert into {} (transaction_hash, scope, subject, from_public_key, to_public_key, timestamp) values ('{}', '{}', '{}', '{}', '{}', '{}')",
+            OWNERSHIP_TRANSFERS_TABLE_NAME,
+            transfer.transaction_hash,
+            transfer.scope,
+            transfer.subject,
+            transfer.from_public_key,
+            transfer.to_public_key,
+            transfer.timestamp,
+        );
+
+        let result = Storage::execute(s);
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                log::info!("{}", e);
+                Err(InternalError(e.to_string()))
+            }
+        }
+    }
+
+    /**
+     * Owners of a metadata record or token_key, oldest transfer first
+     */
+    pub fn get_ownership_transfers(&self, subject: String) -> Result<Vec<OwnershipTransfer>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE subject = '{}' ORDER BY timestamp ASC, rowid ASC",
+            OWNERSHIP_TRANSFERS_TABLE_NAME, subject
+        );
+
+        let result = Storage::read(statement)?;
+        read(result)
+    }
+
+    /**
+     * Moves a metadata record to a new owner. The hash is derived from the owner, so the record
+     * and everything attached to it are re-keyed. Returns the new hash
+     */
+    pub fn transfer_metadata(&self, metadata: &Metadata, public_key: String) -> Result<String, ServiceError> {
+        let hash = Metadata::generate_hash(
+            metadata.data_key.clone(),
+            metadata.meta_contract_id.clone(),
+            metadata.alias.clone(),
+            public_key.clone(),
+            metadata.version.clone(),
+        );
+
+        let s = format!(
+            "update {} set public_key = '{}' where hash = '{}'",
+            METADATAS_TABLE_NAME, public_key, metadata.hash
+        );
+
+        Storage::execute(s)?;
+        self.rekey_metadata(&metadata.hash, &hash, &metadata.data_key, &metadata.token_key)?;
+
+        Ok(hash)
+    }
+
+    /**
+     * Moves the default meta contract of a token_key to a new owner, with its binding and
+     * the registration the previous owner held
+     */
+    pub fn transfer_meta_contract(&self, contract: &MetaContract, public_key: String) -> Result<(), ServiceError> {
+        let statements = vec![
+            format!(
+                "update {} set public_key = '{}' where token_key = '{}'",
+                META_CONTRACT_TABLE_NAME, public_key, contract.token_key
+            ),
+            format!(
+                "update {} set public_key = '{}' where token_key = '{}' AND meta_contract_id = '{}'",
+                META_CONTRACT_BINDINGS_TABLE_NAME, public_key, contract.token_key, contract.meta_contract_id
+            ),
+            format!(
+                "update {} set public_key = '{}' where meta_contract_id = '{}' AND public_key = '{}'",
+                META_CONTRACT_REGISTRATIONS_TABLE_NAME, public_key, contract.meta_contract_id, contract.public_key
+            ),
+        ];
+
+        for statement in statements {
+            Storage::execute(statement)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn read(result: RQLiteResult) -> Result<Vec<OwnershipTransfer>, ServiceError> {
+    let mut transfers = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows {
+            match row {
+                Row::OwnershipTransfer(transfer) => transfers.push(transfer),
+                _ => {
+                    return Err(ServiceError::InternalError(format!(
+                        "Invalid data format: {}",
+                        OWNERSHIP_TRANSFERS_TABLE_NAME
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(transfers)
+}
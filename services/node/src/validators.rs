@@ -1,67 +1,84 @@
 use std::time::{UNIX_EPOCH, SystemTime};
 
+use serde_json::json;
+
 use crate::block::Block;
 use crate::cron::{Cron, SerdeCron};
-use crate::data_types::DataTypeClone;
-use crate::defaults::{CRON_ACTION_CREATE, CRON_ACTION_UPDATE, CRON_ACTION_UPDATE_STATUS, CRON_STATUS_ENABLE, RECEIPT_STATUS_FAILED, RECEIPT_STATUS_SUCCESS, STATUS_DONE, STATUS_FAILED};
-use crate::metadatas::{FinalMetadata, Metadata, SerdeMetadata};
-use crate::transaction::{TransactionSubset, TransactionReceipt};
+use crate::data_types::{DataTypeClone, ForkPolicyResult};
+use crate::freeze::{MetadataFreeze, SerdeFreeze};
+use crate::grant::{Grant, SerdeGrant};
+use crate::defaults::{FREEZE_SCOPE_DATA_KEY, FREEZE_SCOPE_RECORD, METHOD_CRON, METHOD_DELETE, METHOD_TRANSFER};
+use crate::defaults::{TRANSFER_SCOPE_META_CONTRACT, TRANSFER_SCOPE_METADATA};
+use crate::defaults::{GRANT_ACTION_CREATE, GRANT_ACTION_REVOKE, GRANT_SCOPE_ALIAS, GRANT_SCOPE_TOKEN_KEY};
+use crate::defaults::{CRON_ACTION_CREATE, CRON_ACTION_UPDATE, CRON_ACTION_UPDATE_STATUS, CRON_STATUS_ENABLE, RECEIPT_STATUS_CONFLICT, RECEIPT_STATUS_FAILED, RECEIPT_STATUS_SUCCESS, STATUS_DONE, STATUS_FAILED};
+use crate::metadata_versions::MetadataVersion;
+use crate::metadatas::{FinalMetadata, Metadata, SerdeDelete, SerdeMetadata};
+use crate::native_policy::{is_native, native_policy, NativeContext};
+use crate::patch::patch_metadatas;
+use crate::transaction::{Transaction, TransactionSubset, TransactionReceipt};
 use crate::{error::ServiceError, error::ServiceError::*};
-use crate::{get, put_block};
-use crate::{meta_contract::MetaContract, storage_impl::get_storage};
+use crate::{put_block, unpin};
+use crate::meta_contract::{MetaContract, SerdeMetaContract};
+use crate::storage_impl::get_storage;
+use crate::suspension::{MetaContractSuspension, SerdeSuspend};
+use crate::registration::MetaContractRegistration;
+use crate::transfer::{OwnershipTransfer, SerdeTransfer};
 
 /**
  * Validated meta contract method type
  */
 pub fn validate_meta_contract(transaction_hash: String) {
-    let mut current_meta_contract;
-    let mut is_update = false;
     let mut error: Option<ServiceError> = None;
 
     let storage = get_storage();
 
-    let mut transaction = storage.get_transaction(transaction_hash).unwrap().clone();
+    let transaction = storage.get_transaction(transaction_hash).unwrap().clone();
 
-    let sm_result = storage.get_meta_contract_by_id_and_pk(transaction.meta_contract_id.clone(), transaction.public_key.clone());
+    let cid = serde_json::from_str::<SerdeMetaContract>(&transaction.mcdata)
+        .ok()
+        .and_then(|config| config.cid)
+        .unwrap_or_default();
 
-    let mut is_update = false;
-    match sm_result {
-        Ok(contract) => {
-            if transaction.public_key == contract.public_key {
-                // error = Some(RecordFound(f!("{transaction.public_key}")))
-                is_update = true;
-            } else {
-                current_meta_contract = contract;
-                current_meta_contract.meta_contract_id = transaction.data.clone();
+    let current_meta_contract = MetaContract {
+        token_key: transaction.token_key.clone(),
+        meta_contract_id: transaction.meta_contract_id.clone(),
+        public_key: transaction.public_key.clone(),
+        cid,
+    };
+
+    // the first contract bound to a token_key becomes its default one, later ones run next to it
+    let default_result = if transaction.token_key.is_empty() {
+        storage.get_meta_contract_by_id_and_pk(transaction.meta_contract_id.clone(), transaction.public_key.clone())
+    } else {
+        storage.get_meta_contract_by_tokenkey(transaction.token_key.clone())
+    };
+
+    match default_result {
+        Ok(_) => {}
+        Err(ServiceError::RecordNotFound(_)) => {
+            if let Err(e) = storage.write_meta_contract(current_meta_contract.clone()) {
+                error = Some(e);
             }
         }
-        Err(ServiceError::RecordNotFound(_)) => {}
         Err(e) => error = Some(e),
     }
 
-    if error.is_none() {
-        let meta_result;
-
-        current_meta_contract = MetaContract {
-            token_key: transaction.token_key.clone(),
+    if error.is_none() && !is_native(&transaction.meta_contract_id) {
+        let registration = MetaContractRegistration {
             meta_contract_id: transaction.meta_contract_id.clone(),
             public_key: transaction.public_key.clone(),
-            cid: "".to_string(),
+            transaction_hash: transaction.hash.clone(),
+            registered_at: transaction.timestamp,
         };
 
-        if is_update {
-          meta_result = storage.rebind_meta_contract(
-            transaction.token_key.clone(), 
-            transaction.meta_contract_id.clone(), 
-            transaction.public_key.clone(),
-          );
-        } else {
-          meta_result = storage.write_meta_contract(current_meta_contract);
+        if let Err(e) = storage.register_meta_contract(registration) {
+            error = Some(e);
         }
+    }
 
-        match meta_result {
-            Ok(()) => {}
-            Err(e) => error = Some(e),
+    if error.is_none() {
+        if let Err(e) = apply_meta_contract_config(&transaction) {
+            error = Some(e);
         }
     }
 
@@ -70,6 +87,22 @@ pub fn validate_meta_contract(transaction_hash: String) {
     let now = SystemTime::now();
     let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
 
+    if error.is_none() && !current_meta_contract.token_key.is_empty() {
+        let result = storage
+            .bind_meta_contract(&current_meta_contract, transaction.hash.clone(), timestamp.as_millis() as u64)
+            .and_then(|_| {
+                storage.write_meta_contract_version(
+                    &current_meta_contract,
+                    transaction.hash.clone(),
+                    timestamp.as_millis() as u64,
+                )
+            });
+
+        if let Err(e) = result {
+            error = Some(e);
+        }
+    }
+
     if !error.is_none() {
         error_text = error.unwrap().to_string();
         status = RECEIPT_STATUS_FAILED;
@@ -85,12 +118,196 @@ pub fn validate_meta_contract(transaction_hash: String) {
       timestamp: timestamp.as_millis() as u64,
       error_text,
       data: "".to_string(),
+      meta_contract_version: 0,
     };
     storage.write_transaction_receipt(receipt);
 
     storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
 }
 
+/**
+ * Validated "suspend" method type. Crons of a suspended meta contract are disabled,
+ * and enabled again when the suspension is lifted
+ */
+pub fn validate_suspend(transaction_hash: String) {
+    let storage = get_storage();
+    let transaction = storage.get_transaction(transaction_hash).unwrap();
+
+    let mut status = RECEIPT_STATUS_SUCCESS;
+    let mut error_text = "".to_string();
+
+    let result = serde_json::from_str::<SerdeSuspend>(&transaction.data)
+        .map_err(|e| InvalidDataFormatForMethodType(e.to_string()))
+        .and_then(|serde_suspend| {
+            if serde_suspend.suspended {
+                storage.write_meta_contract_suspension(MetaContractSuspension {
+                    meta_contract_id: transaction.meta_contract_id.clone(),
+                    public_key: transaction.public_key.clone(),
+                    transaction_hash: transaction.hash.clone(),
+                    timestamp: transaction.timestamp,
+                })?;
+                storage.cron_disable(transaction.meta_contract_id.clone(), transaction.hash.clone())
+            } else {
+                storage.delete_meta_contract_suspension(transaction.meta_contract_id.clone())?;
+                storage.cron_enable(transaction.meta_contract_id.clone())
+            }
+        });
+
+    if let Err(e) = result {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
+    }
+
+    let now = SystemTime::now();
+    let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
+
+    let receipt = TransactionReceipt {
+      hash: transaction.hash.clone(),
+      meta_contract_id: transaction.meta_contract_id.clone(),
+      status,
+      timestamp: timestamp.as_millis() as u64,
+      error_text,
+      data: "".to_string(),
+      meta_contract_version: 0,
+    };
+    let _ = storage.write_transaction_receipt(receipt);
+
+    let _ = storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
+}
+
+/**
+ * Validated "unbind" method type. When the default contract of a token_key is unbound,
+ * the next bound contract takes its place
+ */
+pub fn validate_unbind(transaction_hash: String) {
+    let storage = get_storage();
+    let transaction = storage.get_transaction(transaction_hash).unwrap();
+
+    let mut status = RECEIPT_STATUS_SUCCESS;
+    let mut error_text = "".to_string();
+
+    let result = storage
+        .unbind_meta_contract(transaction.token_key.clone(), transaction.meta_contract_id.clone())
+        .and_then(|_| {
+            let default = storage.get_meta_contract_by_tokenkey(transaction.token_key.clone())?;
+
+            if default.meta_contract_id != transaction.meta_contract_id {
+                return Ok(());
+            }
+
+            match storage.get_bound_meta_contracts(transaction.token_key.clone())?.first() {
+                Some(next) => {
+                    storage.set_default_meta_contract(next)?;
+                    storage.write_meta_contract_version(next, transaction.hash.clone(), transaction.timestamp)
+                }
+                None => storage.delete_meta_contract(transaction.token_key.clone()),
+            }
+        });
+
+    if let Err(e) = result {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
+    }
+
+    let now = SystemTime::now();
+    let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
+
+    let receipt = TransactionReceipt {
+      hash: transaction.hash.clone(),
+      meta_contract_id: transaction.meta_contract_id.clone(),
+      status,
+      timestamp: timestamp.as_millis() as u64,
+      error_text,
+      data: "".to_string(),
+      meta_contract_version: 0,
+    };
+    let _ = storage.write_transaction_receipt(receipt);
+
+    let _ = storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
+}
+
+/**
+ * Applies the settings sent in the `mcdata` of a contract transaction.
+ * Settings that are left out keep their current value
+ */
+fn apply_meta_contract_config(transaction: &Transaction) -> Result<(), ServiceError> {
+    if transaction.mcdata.is_empty() {
+        return Ok(());
+    }
+
+    let config: SerdeMetaContract = serde_json::from_str(&transaction.mcdata)
+        .map_err(|e| InvalidDataFormatForMethodType(e.to_string()))?;
+
+    let storage = get_storage();
+
+    if let Some(mut paths) = config.indexed_paths {
+        check_registrant(transaction)?;
+
+        let mut current = storage.get_index_paths(transaction.meta_contract_id.clone())?;
+        current.sort();
+        paths.sort();
+        paths.dedup();
+
+        if current != paths {
+            storage.set_index_paths(transaction.meta_contract_id.clone(), paths)?;
+            reindex_meta_contract(&transaction.meta_contract_id)?;
+        }
+    }
+
+    if let Some(profiles) = config.profiles {
+        check_registrant(transaction)?;
+        storage.set_metadata_profiles(transaction.meta_contract_id.clone(), profiles)?;
+    }
+
+    if let Some(policy) = config.policy {
+        check_registrant(transaction)?;
+        storage.set_meta_contract_policy(transaction.meta_contract_id.clone(), policy)?;
+    }
+
+    Ok(())
+}
+
+/**
+ * Extracts the current indexed paths out of every record already written under a meta contract,
+ * so `search_metadatas` also finds the ones written before the paths changed
+ */
+fn reindex_meta_contract(meta_contract_id: &str) -> Result<(), ServiceError> {
+    let storage = get_storage();
+
+    for metadata in storage.get_live_metadatas_by_meta_contract_id(meta_contract_id.to_string())? {
+        if metadata.cid.is_empty() {
+            continue;
+        }
+
+        let content = match Block::fetch(metadata.cid.clone()) {
+            Ok(block) => block.content.to_string(),
+            Err(e) => {
+                log::info!("{}", e);
+                continue;
+            }
+        };
+
+        if let Err(e) = storage.index_metadata_content(metadata.hash.clone(), metadata.meta_contract_id.clone(), &content) {
+            log::info!("{}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * Settings of a meta contract are shared by every collection bound to it, only its registrant changes them
+ */
+fn check_registrant(transaction: &Transaction) -> Result<(), ServiceError> {
+    let storage = get_storage();
+
+    if storage.is_meta_contract_registrant(transaction.meta_contract_id.clone(), &transaction.public_key)? {
+        Ok(())
+    } else {
+        Err(InvalidOwner(format!("not owner of meta contract: {}", transaction.public_key)))
+    }
+}
+
 /**
  * Validated "metadata" method type
  */
@@ -98,7 +315,7 @@ pub fn validate_metadata(
     transaction_hash: String,
     meta_contract_id: String,
     on_metacontract_result: bool,
-    metadatas: Vec<FinalMetadata>,
+    mut metadatas: Vec<FinalMetadata>,
     final_error_msg: String,
 ) {
     let storage = get_storage();
@@ -114,7 +331,30 @@ pub fn validate_metadata(
             error_text = final_error_msg;
         }
         status = RECEIPT_STATUS_FAILED;
+    } else if let Err(e) = check_suspended(&meta_contract_id) {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
+    } else if let Err(e) = check_grant(&transaction) {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
+    } else if let Err(e) = check_publisher_policy(&transaction) {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
+    } else if let Err(e) = check_expected_cid(&transaction) {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_CONFLICT;
+    } else if let Err(e) = check_frozen(&transaction, &metadatas) {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
+    } else if let Err(e) = check_deleted(&transaction, &metadatas) {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
+    } else if let Err(e) = patch_metadatas(&transaction, &mut metadatas) {
+        // a patch that does not apply fails the whole transaction before anything is written
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
     } else {
+        // the receipt stays failed once one record fails
         for data in metadatas {
             let result = storage.get_owner_metadata(
                 transaction.data_key.clone(),
@@ -127,6 +367,10 @@ pub fn validate_metadata(
             log::info!("{:?}", result);
 
             match result {
+                Ok(metadata) if !metadata.tombstone.is_empty() => {
+                    error_text = Deleted(metadata.tombstone).to_string();
+                    status = RECEIPT_STATUS_FAILED;
+                }
                 Ok(metadata) => {
 
                     let tx = TransactionSubset {
@@ -140,19 +384,27 @@ pub fn validate_metadata(
                     let tx_serde = serde_json::to_string(&tx).unwrap();
 
                     let result_ipfs_dag_put =
-                        put_block(data.content, metadata.cid, tx_serde, "".to_string(), 0);
+                        put_block(data.content.clone(), metadata.cid.clone(), tx_serde, "".to_string(), 0);
                     let content_cid = result_ipfs_dag_put.cid;
 
                     let _ = storage.update_cid(
-                        metadata.data_key, 
-                        metadata.meta_contract_id, 
-                        metadata.alias, 
-                        metadata.public_key, 
+                        metadata.data_key.clone(), 
+                        metadata.meta_contract_id.clone(), 
+                        metadata.alias.clone(), 
+                        metadata.public_key.clone(), 
+                        content_cid.clone(),
+                        metadata.version.clone(),
+                    );
+
+                    record_cid_update(
+                        &metadata,
                         content_cid,
-                        metadata.version,
+                        metadata.cid.clone(),
+                        transaction.hash.clone(),
+                        &transaction.method,
+                        transaction.timestamp,
+                        &data.content,
                     );
-                    
-                    status = RECEIPT_STATUS_SUCCESS;
                 }
                 Err(ServiceError::RecordNotFound(_)) => {
 
@@ -167,7 +419,7 @@ pub fn validate_metadata(
                     let tx_serde = serde_json::to_string(&tx).unwrap();
 
                     let result_ipfs_dag_put =
-                        put_block(data.content, "".to_string(), tx_serde, "".to_string(), 0);
+                        put_block(data.content.clone(), "".to_string(), tx_serde, "".to_string(), 0);
                     let content_cid = result_ipfs_dag_put.cid;
 
                     let serde_metadata: Result<SerdeMetadata, serde_json::Error> = serde_json::from_str(&transaction.mcdata.clone());
@@ -183,15 +435,23 @@ pub fn validate_metadata(
                         transaction.token_key.clone(),
                         transaction.meta_contract_id.clone(),
                         data.alias.clone(),
-                        content_cid,
+                        content_cid.clone(),
                         data.public_key.clone(),
                         transaction.version.clone(),
                         loose,
                     );
 
-                    let _ = storage.write_metadata(metadata);
+                    let _ = storage.write_metadata(metadata.clone());
 
-                    status = RECEIPT_STATUS_SUCCESS;
+                    record_cid_update(
+                        &metadata,
+                        content_cid,
+                        "".to_string(),
+                        transaction.hash.clone(),
+                        &transaction.method,
+                        transaction.timestamp,
+                        &data.content,
+                    );
                 }
                 Err(e) => {
                     error_text = e.to_string();
@@ -204,23 +464,238 @@ pub fn validate_metadata(
     let now = SystemTime::now();
     let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
 
+    // each contract bound to the token_key runs the transaction and keeps its own receipt
     let receipt = TransactionReceipt {
       hash: transaction.hash.clone(),
-      meta_contract_id: transaction.meta_contract_id.clone(),
+      meta_contract_id: meta_contract_id.clone(),
       status,
       timestamp: timestamp.as_millis() as u64,
       error_text,
       data: "".to_string(),
+      meta_contract_version: 0,
     };
     storage.write_transaction_receipt(receipt);
 
     storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
 }
 
+/**
+ * Compare-and-swap guard: the record targeted by the transaction must still point to the
+ * CID the client based its change on. A record already moved by this very transaction
+ * (when several meta contracts run for it) is not a conflict
+ */
+fn check_expected_cid(transaction: &Transaction) -> Result<(), ServiceError> {
+    if transaction.expected_cid.is_empty() {
+        return Ok(());
+    }
+
+    let storage = get_storage();
+
+    let result = storage.get_owner_metadata(
+        transaction.data_key.clone(),
+        transaction.meta_contract_id.clone(),
+        transaction.public_key.clone(),
+        transaction.alias.clone(),
+        transaction.version.clone(),
+    );
+
+    let metadata = match result {
+        Ok(metadata) => metadata,
+        Err(ServiceError::RecordNotFound(_)) => {
+            return Err(Conflict(format!("expected {}, found no record", transaction.expected_cid)))
+        }
+        Err(e) => return Err(e),
+    };
+
+    if metadata.cid == transaction.expected_cid {
+        return Ok(());
+    }
+
+    let latest = storage.get_metadata_versions(metadata.hash, "desc".to_string(), 0, 1)?;
+
+    match latest.first() {
+        Some(version) if version.transaction_hash == transaction.hash => Ok(()),
+        _ => Err(Conflict(format!(
+            "expected {}, found {}",
+            transaction.expected_cid, metadata.cid
+        ))),
+    }
+}
+
+/**
+ * A grant used to publish must still be active when the transaction is validated
+ */
+fn check_grant(transaction: &Transaction) -> Result<(), ServiceError> {
+    if transaction.grant_hash.is_empty() {
+        return Ok(());
+    }
+
+    let grant = get_storage().get_grant(transaction.grant_hash.clone())?;
+
+    if grant.grantor != transaction.public_key || !grant.allows(&transaction.method) {
+        return Err(InvalidGrant(grant.hash));
+    }
+
+    if !grant.is_active(transaction.timestamp) {
+        return Err(InvalidGrant(format!("{} is revoked or expired", grant.hash)));
+    }
+
+    Ok(())
+}
+
+/**
+ * The grantor a transaction is stored under and the grantee who signed it both have to pass the policy
+ */
+fn check_publisher_policy(transaction: &Transaction) -> Result<(), ServiceError> {
+    check_policy(&transaction.meta_contract_id, &transaction.public_key, &transaction.alias)?;
+
+    if !transaction.grant_hash.is_empty() {
+        let grant = get_storage().get_grant(transaction.grant_hash.clone())?;
+        check_policy(&transaction.meta_contract_id, &grant.grantee, &transaction.alias)?;
+    }
+
+    Ok(())
+}
+
+/**
+ * A suspended meta contract cannot write metadata, even for transactions published before the suspension
+ */
+fn check_suspended(meta_contract_id: &str) -> Result<(), ServiceError> {
+    if is_native(meta_contract_id) {
+        return Ok(());
+    }
+
+    match get_storage().get_meta_contract_suspension(meta_contract_id.to_string())? {
+        Some(suspension) => Err(Suspended(format!("{} by {}", meta_contract_id, suspension.transaction_hash))),
+        None => Ok(()),
+    }
+}
+
+/**
+ * Checked again when the transaction runs, the policy may have changed since it was published
+ */
+fn check_policy(meta_contract_id: &str, public_key: &str, alias: &str) -> Result<(), ServiceError> {
+    if is_native(meta_contract_id) {
+        return Ok(());
+    }
+
+    match get_storage().get_meta_contract_policy(meta_contract_id.to_string())? {
+        Some(policy) if !policy.allows(public_key, alias) => {
+            Err(NotAllowed(format!("{} on {}", public_key, meta_contract_id)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/**
+ * Frozen records, or records under a frozen data_key, cannot be written anymore
+ */
+fn check_frozen(transaction: &Transaction, metadatas: &[FinalMetadata]) -> Result<(), ServiceError> {
+    let storage = get_storage();
+
+    for data in metadatas {
+        let freeze = storage.get_metadata_freeze(
+            transaction.data_key.clone(),
+            transaction.meta_contract_id.clone(),
+            data.public_key.clone(),
+            data.alias.clone(),
+            transaction.version.clone(),
+        )?;
+
+        if let Some(freeze) = freeze {
+            return Err(Frozen(format!("{} frozen by {}", data.alias, freeze.transaction_hash)));
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * The copy is written on the target data_key under the origin's meta contract, which must not be frozen there
+ */
+fn check_clone_target(transaction: &Transaction, data_clone: &DataTypeClone) -> Result<(), ServiceError> {
+    let freeze = get_storage().get_metadata_freeze(
+        transaction.data_key.clone(),
+        data_clone.origin_meta_contract_id.clone(),
+        transaction.public_key.clone(),
+        transaction.alias.clone(),
+        data_clone.origin_version.clone(),
+    )?;
+
+    match freeze {
+        Some(freeze) => Err(Frozen(format!("{} frozen by {}", transaction.data_key, freeze.transaction_hash))),
+        None => Ok(()),
+    }
+}
+
+/**
+ * A deleted record fails the whole transaction before any of its records is written
+ */
+fn check_deleted(transaction: &Transaction, metadatas: &[FinalMetadata]) -> Result<(), ServiceError> {
+    let storage = get_storage();
+
+    for data in metadatas {
+        let result = storage.get_owner_metadata(
+            transaction.data_key.clone(),
+            transaction.meta_contract_id.clone(),
+            data.public_key.clone(),
+            data.alias.clone(),
+            transaction.version.clone(),
+        );
+
+        match result {
+            Ok(metadata) if !metadata.tombstone.is_empty() => return Err(Deleted(metadata.tombstone)),
+            Ok(_) | Err(ServiceError::RecordNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * Validated "metadata" method type for a native meta contract, evaluated in the node.
+ * The policy sees the records of the transaction's meta contract and version
+ */
+pub fn validate_metadata_native(transaction_hash: String, meta_contract_id: String) {
+    let storage = get_storage();
+    let transaction = storage.get_transaction(transaction_hash.clone()).unwrap();
+
+    let result = match native_policy(&meta_contract_id) {
+        Some(policy) => storage
+            .get_metadata_by_datakey_and_version(transaction.data_key.clone(), transaction.version.clone())
+            .map_err(|e| e.to_string())
+            .and_then(|metadatas| {
+                let metadatas: Vec<Metadata> = metadatas
+                    .into_iter()
+                    .filter(|metadata| metadata.meta_contract_id == transaction.meta_contract_id)
+                    .collect();
+
+                let token_owner = storage
+                    .get_meta_contract_by_tokenkey(transaction.token_key.clone())
+                    .map(|meta_contract| meta_contract.public_key)
+                    .unwrap_or_default();
+
+                policy.execute(&NativeContext {
+                    transaction: &transaction,
+                    metadatas: &metadatas,
+                    token_owner,
+                })
+            }),
+        None => Err(format!("unknown native meta contract: {}", meta_contract_id)),
+    };
+
+    match result {
+        Ok(metadatas) => validate_metadata(transaction_hash, meta_contract_id, true, metadatas, "".to_string()),
+        Err(error) => validate_metadata(transaction_hash, meta_contract_id, false, vec![], error),
+    }
+}
+
 /**
  * Validated "metadata cron" method type
  */
 pub fn validate_metadata_cron(
+  cron_tx_hash: String,
   meta_contract: MetaContract,
   data_key: String,
   on_metacontract_result: bool,
@@ -228,6 +703,12 @@ pub fn validate_metadata_cron(
 ) {
   let storage = get_storage();
 
+  // events of a suspended contract write nothing until the suspension is lifted
+  if let Err(e) = check_suspended(&meta_contract.meta_contract_id) {
+      log::info!("{}", e);
+      return;
+  }
+
   if on_metacontract_result {
       for data in metadatas {
           let result = storage.get_owner_metadata(
@@ -240,104 +721,404 @@ pub fn validate_metadata_cron(
 
           log::info!("{:?}", result);
 
-          match result {
-              Ok(_) => {}
-              Err(ServiceError::RecordNotFound(_)) => {
+          // a record a reorg took back is minted again when its event is indexed again
+          let restored = match result {
+              Ok(metadata) if !metadata.tombstone.is_empty() && storage.get_cron_tx(metadata.tombstone.clone()).is_ok() => {
+                  Some(metadata)
+              }
+              Ok(_) => continue,
+              Err(ServiceError::RecordNotFound(_)) => None,
+              Err(_) => continue,
+          };
+
+          let freeze = storage.get_metadata_freeze(
+              data_key.clone(),
+              meta_contract.meta_contract_id.clone(),
+              data.public_key.clone(),
+              data.alias.clone(),
+              "".to_string(),
+          );
 
-                  let result_ipfs_dag_put =
-                      put_block(data.content, "".to_string(), "{}".to_string(), "".to_string(), 0);
-                  let content_cid = result_ipfs_dag_put.cid;
+          if !matches!(freeze, Ok(None)) {
+              continue;
+          }
 
+          let result_ipfs_dag_put =
+              put_block(data.content.clone(), "".to_string(), "{}".to_string(), "".to_string(), 0);
+          let content_cid = result_ipfs_dag_put.cid;
+
+          let metadata = match restored {
+              Some(metadata) => {
+                  if let Err(e) = storage.restore_metadata(metadata.hash.clone(), content_cid.clone()) {
+                      log::info!("{}", e);
+                      continue;
+                  }
+                  metadata
+              }
+              None => {
                   let metadata = Metadata::new(
                       data_key.clone(),
                       meta_contract.token_key.clone(),
                       meta_contract.meta_contract_id.clone(),
                       data.alias.clone(),
-                      content_cid,
+                      content_cid.clone(),
                       data.public_key.clone(),
                       "".to_string(),
                       data.loose.clone(),
                   );
 
-                  let _ = storage.write_metadata(metadata);
+                  let _ = storage.write_metadata(metadata.clone());
+                  metadata
               }
-              Err(_) => {}
           };
+
+          let now = SystemTime::now();
+          let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
+
+          record_cid_update(
+              &metadata,
+              content_cid,
+              "".to_string(),
+              cron_tx_hash.clone(),
+              METHOD_CRON,
+              timestamp.as_millis() as u64,
+              &data.content,
+          );
       }
   }
 }
 
 /**
- * Validated "clone" method type
- * Fetch the origin metadata content from Block and clone it to the new metadata
+ * Validated "clone" method type. The fork policy of the origin meta contract decides,
+ * and its verdict is kept in the receipt data
  */
 pub fn validate_clone(
     transaction_hash: String,
     meta_contract_id: String,
-    on_metacontract_result: bool,
-    data: String,
-    final_error_msg: String,
+    policy: ForkPolicyResult,
 ) {
     let storage = get_storage();
-    let mut transaction = storage.get_transaction(transaction_hash).unwrap().clone();
+    let transaction = storage.get_transaction(transaction_hash).unwrap();
 
     let mut status = RECEIPT_STATUS_FAILED;
     let mut error_text = "".to_string();
-    if !on_metacontract_result {
-        status = RECEIPT_STATUS_FAILED;
-        if final_error_msg.is_empty() {
-            error_text = "Metadata not forkable".to_string();
-        } else {
-            error_text = final_error_msg;
-        }
+
+    let data_clone: Result<DataTypeClone, ServiceError> = serde_json::from_str(&transaction.data)
+        .map_err(|e| InvalidDataFormatForMethodType(e.to_string()));
+
+    let result = data_clone.and_then(|data_clone| {
+        check_fork_policy(&policy, &data_clone)?;
+        check_suspended(&data_clone.origin_meta_contract_id)?;
+        check_policy(&data_clone.origin_meta_contract_id, &transaction.public_key, &transaction.alias)?;
+        check_clone_target(&transaction, &data_clone)?;
+        Ok(data_clone)
+    });
+
+    let fee_transaction = result
+        .as_ref()
+        .map(|data_clone| data_clone.fee_transaction.clone())
+        .unwrap_or_default();
+
+    match result.and_then(|data_clone| write_clone(&transaction, &meta_contract_id, &data_clone)) {
+        Ok(_) => status = RECEIPT_STATUS_SUCCESS,
+        Err(e) => error_text = e.to_string(),
+    }
+
+    let now = SystemTime::now();
+    let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
+
+    let verdict = json!({
+        "allowed": policy.allowed,
+        "attribution": policy.attribution,
+        "fee": policy.fee,
+        "fee_transaction": fee_transaction,
+    });
+
+    let receipt = TransactionReceipt {
+      hash: transaction.hash.clone(),
+      meta_contract_id,
+      status,
+      timestamp: timestamp.as_millis() as u64,
+      error_text,
+      data: verdict.to_string(),
+      meta_contract_version: 0,
+    };
+    let _ = storage.write_transaction_receipt(receipt);
+
+    let _ = storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
+}
+
+/**
+ * Copies the origin block onto the target data_key, owned by the signer, and links the two records
+ */
+fn write_clone(transaction: &Transaction, meta_contract_id: &str, data_clone: &DataTypeClone) -> Result<(), ServiceError> {
+    let storage = get_storage();
+
+    let origin_metadata = storage.get_owner_metadata(
+        data_clone.origin_data_key.clone(),
+        data_clone.origin_meta_contract_id.clone(),
+        data_clone.origin_public_key.clone(),
+        data_clone.origin_alias.clone(),
+        data_clone.origin_version.clone(),
+    )?;
+
+    let tx = TransactionSubset {
+        hash: transaction.hash.clone(),
+        timestamp: transaction.timestamp,
+        meta_contract_id: meta_contract_id.to_string(),
+        method: transaction.method.clone(),
+        value: serde_json::to_string(data_clone)?,
+    };
+
+    let tx_serde = serde_json::to_string(&tx)?;
+
+    let block = Block::fetch(origin_metadata.cid.clone())?;
+    let content = serde_json::to_string(&block.content)?;
+
+    let result_ipfs_dag_put = put_block(
+        content.clone(),
+        "".to_string(),
+        tx_serde,
+        "".to_string(),
+        0,
+    );
+
+    let token_key = if transaction.token_key.is_empty() {
+        origin_metadata.token_key.clone()
     } else {
-        let data_clone: DataTypeClone = serde_json::from_str(&data.clone()).unwrap();
-
-        let origin_metadata = storage.get_owner_metadata(
-            data_clone.origin_data_key.clone(),
-            data_clone.origin_meta_contract_id.clone(),
-            data_clone.origin_public_key.clone(),
-            data_clone.origin_alias.clone(),
-            data_clone.origin_version.clone(),
-        ).unwrap();
-
-        let tx = TransactionSubset {
-            hash: transaction.hash.clone(),
-            timestamp: transaction.timestamp.clone(),
-            meta_contract_id: meta_contract_id.clone(),
-            method: transaction.method.clone(),
-            value: serde_json::to_string(&data_clone).unwrap(),
+        transaction.token_key.clone()
+    };
+
+    // the signer owns the copy, under the alias resolved by publish
+    let metadata = Metadata::new(
+        transaction.data_key.clone(),
+        token_key,
+        origin_metadata.meta_contract_id.clone(),
+        transaction.alias.clone(),
+        result_ipfs_dag_put.cid.clone(),
+        transaction.public_key.clone(),
+        origin_metadata.version.clone(),
+        origin_metadata.loose,
+    );
+
+    storage.write_metadata(metadata.clone())?;
+
+    record_cid_update(
+        &metadata,
+        result_ipfs_dag_put.cid,
+        "".to_string(),
+        transaction.hash.clone(),
+        &transaction.method,
+        transaction.timestamp,
+        &content,
+    );
+
+    storage.write_clone_edge(
+        origin_metadata.hash.clone(),
+        metadata.hash.clone(),
+        transaction.hash.clone(),
+        transaction.timestamp,
+    )?;
+
+    Ok(())
+}
+
+/**
+ * Clone whose origin or origin meta contract could not be read. It fails instead of staying pending
+ */
+pub fn validate_clone_failed(transaction_hash: String, error_text: String) {
+    let storage = get_storage();
+
+    let transaction = match storage.get_transaction(transaction_hash) {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            log::info!("{}", e);
+            return;
+        }
+    };
+
+    let now = SystemTime::now();
+    let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
+
+    let receipt = TransactionReceipt {
+      hash: transaction.hash.clone(),
+      meta_contract_id: transaction.meta_contract_id.clone(),
+      status: RECEIPT_STATUS_FAILED,
+      timestamp: timestamp.as_millis() as u64,
+      error_text,
+      data: "".to_string(),
+      meta_contract_version: 0,
+    };
+    let _ = storage.write_transaction_receipt(receipt);
+
+    let _ = storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
+}
+
+/**
+ * A clone is refused when forking is denied, when an attribution field is missing,
+ * or when a fee is asked and no payment is referenced. The reference itself is recorded, not verified
+ */
+fn check_fork_policy(policy: &ForkPolicyResult, data_clone: &DataTypeClone) -> Result<(), ServiceError> {
+    if !policy.allowed {
+        let reason = if policy.error_string.is_empty() {
+            "Metadata not forkable".to_string()
+        } else {
+            policy.error_string.clone()
         };
+        return Err(ForkDenied(reason));
+    }
+
+    for field in policy.attribution.iter() {
+        let given = data_clone.attribution.get(field).map(|value| !value.is_empty()).unwrap_or(false);
 
-        let tx_serde = serde_json::to_string(&tx).unwrap();
+        if !given {
+            return Err(ForkDenied(format!("missing attribution: {}", field)));
+        }
+    }
 
-        let ipfs_get_result = get(origin_metadata.cid, "".to_string(), 0);
+    if !policy.fee.is_empty() && data_clone.fee_transaction.is_empty() {
+        return Err(ForkDenied(format!("fee of {} not paid", policy.fee)));
+    }
 
-        let block: Block = serde_json::from_str(&ipfs_get_result.block).unwrap();
-        let content = block.content;
+    Ok(())
+}
 
-        let result_ipfs_dag_put = put_block(
-            serde_json::to_string(&content).unwrap(),
-            "".to_string(),
-            tx_serde,
-            "".to_string(),
-            0,
-        );
+/**
+ * Clone of a record written by a native meta contract. Native policies decide forks in the node
+ */
+pub fn validate_clone_native(transaction_hash: String, meta_contract_id: String) {
+    let policy = match native_policy(&meta_contract_id) {
+        Some(native) => native.fork_policy(),
+        None => ForkPolicyResult {
+            allowed: false,
+            error_string: format!("unknown native meta contract: {}", meta_contract_id),
+            ..Default::default()
+        },
+    };
+
+    validate_clone(transaction_hash, meta_contract_id, policy);
+}
+
+/**
+ * Validated "freeze" method type
+ */
+pub fn validate_freeze(transaction_hash: String) {
+    let storage = get_storage();
+    let transaction = storage.get_transaction(transaction_hash).unwrap();
 
-        let metadata = Metadata::new(
+    let mut status = RECEIPT_STATUS_SUCCESS;
+    let mut error_text = "".to_string();
+
+    let result = check_grant(&transaction)
+        .and_then(|_| {
+            serde_json::from_str::<SerdeFreeze>(&transaction.data)
+                .map_err(|e| InvalidDataFormatForMethodType(e.to_string()))
+        })
+        .and_then(|serde_freeze| {
+            // a data_key freeze covers every record, it is not tied to one
+            let (public_key, alias, version) = match serde_freeze.scope.as_str() {
+                FREEZE_SCOPE_RECORD => (
+                    transaction.public_key.clone(),
+                    transaction.alias.clone(),
+                    transaction.version.clone(),
+                ),
+                FREEZE_SCOPE_DATA_KEY => ("".to_string(), "".to_string(), "".to_string()),
+                _ => return Err(InvalidDataFormatForMethodType(serde_freeze.scope)),
+            };
+
+            storage.write_metadata_freeze(MetadataFreeze {
+                scope: serde_freeze.scope,
+                data_key: transaction.data_key.clone(),
+                meta_contract_id: transaction.meta_contract_id.clone(),
+                public_key,
+                alias,
+                version,
+                transaction_hash: transaction.hash.clone(),
+                timestamp: transaction.timestamp,
+            })
+        });
+
+    if let Err(e) = result {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
+    }
+
+    let now = SystemTime::now();
+    let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
+
+    let receipt = TransactionReceipt {
+      hash: transaction.hash.clone(),
+      meta_contract_id: transaction.meta_contract_id.clone(),
+      status,
+      timestamp: timestamp.as_millis() as u64,
+      error_text,
+      data: "".to_string(),
+      meta_contract_version: 0,
+    };
+    let _ = storage.write_transaction_receipt(receipt);
+
+    let _ = storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
+}
+
+/**
+ * Validated "delete" method type
+ * Tombstones the record and records the deletion in its history, optionally unpinning its blocks
+ */
+pub fn validate_delete(transaction_hash: String) {
+    let storage = get_storage();
+    let transaction = storage.get_transaction(transaction_hash).unwrap();
+
+    let mut status = RECEIPT_STATUS_SUCCESS;
+    let mut error_text = "".to_string();
+
+    let serde_delete: SerdeDelete = serde_json::from_str(&transaction.data).unwrap_or_default();
+
+    let result = check_grant(&transaction)
+        .and_then(|_| storage.get_owner_metadata(
             transaction.data_key.clone(),
-            origin_metadata.token_key.clone(),
-            origin_metadata.meta_contract_id.clone(),
-            origin_metadata.alias.clone(),
-            result_ipfs_dag_put.cid,
-            origin_metadata.public_key.clone(),
-            origin_metadata.version.clone(),
-            origin_metadata.loose.clone(),
-        );
+            transaction.meta_contract_id.clone(),
+            transaction.public_key.clone(),
+            transaction.alias.clone(),
+            transaction.version.clone(),
+        ))
+        .and_then(|metadata| {
+            if !metadata.tombstone.is_empty() {
+                return Err(Deleted(metadata.tombstone));
+            }
 
-        let _ = storage.write_metadata(metadata);
+            let freeze = storage.get_metadata_freeze(
+                metadata.data_key.clone(),
+                metadata.meta_contract_id.clone(),
+                metadata.public_key.clone(),
+                metadata.alias.clone(),
+                metadata.version.clone(),
+            )?;
 
-        status = RECEIPT_STATUS_SUCCESS;
+            if let Some(freeze) = freeze {
+                return Err(Frozen(format!("frozen by {}", freeze.transaction_hash)));
+            }
+
+            storage.tombstone_metadata(metadata.hash.clone(), transaction.hash.clone())?;
+
+            storage.write_metadata_version(MetadataVersion::new(
+                metadata.hash.clone(),
+                "".to_string(),
+                metadata.cid.clone(),
+                transaction.hash.clone(),
+                METHOD_DELETE.to_string(),
+                transaction.timestamp,
+            ))?;
+
+            if serde_delete.unpin {
+                unpin_chain(metadata.cid);
+            }
+
+            Ok(())
+        });
+
+    if let Err(e) = result {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
     }
 
     let now = SystemTime::now();
@@ -350,10 +1131,226 @@ pub fn validate_clone(
       timestamp: timestamp.as_millis() as u64,
       error_text,
       data: "".to_string(),
+      meta_contract_version: 0,
     };
-    storage.write_transaction_receipt(receipt);
+    let _ = storage.write_transaction_receipt(receipt);
 
-    storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
+    let _ = storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
+}
+
+/**
+ * Validated "transfer" method type
+ * Moves the record or the meta contract binding to the new owner and records both owners
+ */
+pub fn validate_transfer(transaction_hash: String) {
+    let storage = get_storage();
+    let transaction = storage.get_transaction(transaction_hash).unwrap();
+
+    let mut status = RECEIPT_STATUS_SUCCESS;
+    let mut error_text = "".to_string();
+
+    let result = serde_json::from_str::<SerdeTransfer>(&transaction.data)
+        .map_err(|e| InvalidDataFormatForMethodType(e.to_string()))
+        .and_then(|serde_transfer| {
+            let subject = match serde_transfer.scope.as_str() {
+                TRANSFER_SCOPE_METADATA => {
+                    let metadata = storage.get_owner_metadata(
+                        transaction.data_key.clone(),
+                        transaction.meta_contract_id.clone(),
+                        transaction.public_key.clone(),
+                        transaction.alias.clone(),
+                        transaction.version.clone(),
+                    )?;
+
+                    // the record may have been deleted, frozen or taken by the new owner since publish
+                    check_transferable(&metadata, &serde_transfer.new_public_key)?;
+
+                    let hash = storage.transfer_metadata(&metadata, serde_transfer.new_public_key.clone())?;
+
+                    // same content, the version only marks the change of owner
+                    storage.write_metadata_version(MetadataVersion::new(
+                        hash.clone(),
+                        metadata.cid.clone(),
+                        metadata.cid,
+                        transaction.hash.clone(),
+                        METHOD_TRANSFER.to_string(),
+                        transaction.timestamp,
+                    ))?;
+
+                    hash
+                }
+                TRANSFER_SCOPE_META_CONTRACT => {
+                    let meta_contract = storage.get_meta_contract_by_tokenkey(transaction.token_key.clone())?;
+
+                    if meta_contract.public_key != transaction.public_key {
+                        return Err(InvalidOwner(transaction.public_key.clone()));
+                    }
+
+                    storage.transfer_meta_contract(&meta_contract, serde_transfer.new_public_key.clone())?;
+
+                    meta_contract.token_key
+                }
+                _ => return Err(InvalidDataFormatForMethodType(serde_transfer.scope)),
+            };
+
+            storage.write_ownership_transfer(OwnershipTransfer {
+                transaction_hash: transaction.hash.clone(),
+                scope: serde_transfer.scope,
+                subject,
+                from_public_key: transaction.public_key.clone(),
+                to_public_key: serde_transfer.new_public_key,
+                timestamp: transaction.timestamp,
+            })
+        });
+
+    if let Err(e) = result {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
+    }
+
+    let now = SystemTime::now();
+    let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
+
+    let receipt = TransactionReceipt {
+      hash: transaction.hash.clone(),
+      meta_contract_id: transaction.meta_contract_id.clone(),
+      status,
+      timestamp: timestamp.as_millis() as u64,
+      error_text,
+      data: "".to_string(),
+      meta_contract_version: 0,
+    };
+    let _ = storage.write_transaction_receipt(receipt);
+
+    let _ = storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
+}
+
+/**
+ * Same checks as at publish, against the state the transfer is applied to
+ */
+fn check_transferable(metadata: &Metadata, new_public_key: &str) -> Result<(), ServiceError> {
+    let storage = get_storage();
+
+    if !metadata.tombstone.is_empty() {
+        return Err(Deleted(metadata.tombstone.clone()));
+    }
+
+    let freeze = storage.get_metadata_freeze(
+        metadata.data_key.clone(),
+        metadata.meta_contract_id.clone(),
+        metadata.public_key.clone(),
+        metadata.alias.clone(),
+        metadata.version.clone(),
+    )?;
+
+    if let Some(freeze) = freeze {
+        return Err(Frozen(format!("frozen by {}", freeze.transaction_hash)));
+    }
+
+    let existing = storage.get_owner_metadata(
+        metadata.data_key.clone(),
+        metadata.meta_contract_id.clone(),
+        new_public_key.to_string(),
+        metadata.alias.clone(),
+        metadata.version.clone(),
+    );
+
+    match existing {
+        Ok(_) => Err(RecordFound(format!("{} already owns this record", new_public_key))),
+        Err(ServiceError::RecordNotFound(_)) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/**
+ * Validated "grant" method type
+ */
+pub fn validate_grant(transaction_hash: String) {
+    let storage = get_storage();
+    let transaction = storage.get_transaction(transaction_hash).unwrap();
+
+    let mut status = RECEIPT_STATUS_SUCCESS;
+    let mut error_text = "".to_string();
+
+    let result = serde_json::from_str::<SerdeGrant>(&transaction.data)
+        .map_err(|e| InvalidDataFormatForMethodType(e.to_string()))
+        .and_then(|serde_grant| match serde_grant.action.as_str() {
+            GRANT_ACTION_CREATE => storage.write_grant(Grant {
+                hash: transaction.hash.clone(),
+                grantor: transaction.public_key.clone(),
+                grantee: serde_grant.grantee,
+                token_key: transaction.token_key.clone(),
+                data_key: if serde_grant.scope == GRANT_SCOPE_TOKEN_KEY {
+                    "".to_string()
+                } else {
+                    transaction.data_key.clone()
+                },
+                meta_contract_id: transaction.meta_contract_id.clone(),
+                alias: if serde_grant.scope == GRANT_SCOPE_ALIAS {
+                    transaction.alias.clone()
+                } else {
+                    "".to_string()
+                },
+                scope: serde_grant.scope,
+                methods: serde_grant.methods.join(","),
+                expiry: serde_grant.expiry,
+                revoked: "".to_string(),
+                timestamp: transaction.timestamp,
+            }),
+            GRANT_ACTION_REVOKE => {
+                let grant = storage.get_grant(serde_grant.grant_hash)?;
+
+                if grant.grantor != transaction.public_key {
+                    return Err(InvalidOwner(transaction.public_key.clone()));
+                }
+
+                storage.revoke_grant(grant.hash, transaction.hash.clone())
+            }
+            _ => Err(InvalidDataFormatForMethodType(serde_grant.action)),
+        });
+
+    if let Err(e) = result {
+        error_text = e.to_string();
+        status = RECEIPT_STATUS_FAILED;
+    }
+
+    let now = SystemTime::now();
+    let timestamp = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
+
+    let receipt = TransactionReceipt {
+      hash: transaction.hash.clone(),
+      meta_contract_id: transaction.meta_contract_id.clone(),
+      status,
+      timestamp: timestamp.as_millis() as u64,
+      error_text,
+      data: "".to_string(),
+      meta_contract_version: 0,
+    };
+    let _ = storage.write_transaction_receipt(receipt);
+
+    let _ = storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
+}
+
+/**
+ * Unpins every block of a metadata chain, newest first. Blocks already gone are skipped
+ */
+fn unpin_chain(cid: String) {
+    let mut read_metadata_cid = Some(cid);
+
+    while let Some(cid) = read_metadata_cid.filter(|cid| !cid.is_empty()) {
+        read_metadata_cid = match Block::fetch(cid.clone()) {
+            Ok(block) => block.previous_cid(),
+            Err(e) => {
+                log::info!("{}", e);
+                None
+            }
+        };
+
+        let result = unpin(cid, "".to_string(), 0);
+        if !result.success {
+            log::info!("{}", result.error);
+        }
+    }
 }
 
 /**
@@ -382,46 +1379,55 @@ pub fn validate_cron(transaction_hash: String) {
         serde_cron.node_url,
         transaction.public_key.clone(),
     );
-
-    match result {
-      Ok(row) => {
-        if transaction.public_key == row.public_key {
-          if !serde_cron.hash.is_empty() {
-            match serde_cron.action.as_str() {
-              CRON_ACTION_UPDATE => {
-                let _ = storage.update_cron(serde_cron.hash, cron);
-              }
-              CRON_ACTION_UPDATE_STATUS => {
-                let _ = storage.update_cron_status(serde_cron.hash, serde_cron.status);
-              }
-              _ => {
-                status = STATUS_FAILED;
-                error_text = "Invalid cron action".to_string();
+    cron.start_block = serde_cron.start_block;
+    cron.end_block = serde_cron.end_block;
+    cron.confirmations = serde_cron.confirmations;
+
+    // a suspended contract gets no crons created, changed or restarted
+    if let Err(e) = check_suspended(&transaction.meta_contract_id) {
+      status = RECEIPT_STATUS_FAILED;
+      error_text = e.to_string();
+    } else {
+      match result {
+        Ok(row) => {
+          if transaction.public_key == row.public_key {
+            if !serde_cron.hash.is_empty() {
+              match serde_cron.action.as_str() {
+                CRON_ACTION_UPDATE => {
+                  let _ = storage.update_cron(serde_cron.hash, cron);
+                }
+                CRON_ACTION_UPDATE_STATUS => {
+                  let _ = storage.update_cron_status(serde_cron.hash, serde_cron.status);
+                }
+                _ => {
+                  status = STATUS_FAILED;
+                  error_text = "Invalid cron action".to_string();
+                }
               }
+            } else {
+              status = STATUS_FAILED;
+              error_text = format!("Invalid cron hash: {}", serde_cron.hash);
             }
           } else {
             status = STATUS_FAILED;
-            error_text = format!("Invalid cron hash: {}", serde_cron.hash);
+            error_text = format!("Invalid owner: {}", transaction.public_key);
           }
-        } else {
-          status = STATUS_FAILED;
-          error_text = format!("Invalid owner: {}", transaction.public_key);
         }
-      }
-      Err(ServiceError::RecordNotFound(_)) => {
-        if serde_cron.action == CRON_ACTION_CREATE {
-          cron.status = CRON_STATUS_ENABLE;
+        Err(ServiceError::RecordNotFound(_)) => {
+          if serde_cron.action == CRON_ACTION_CREATE {
+            cron.status = CRON_STATUS_ENABLE;
   
-          let _ = storage.write_cron(cron);
-        } else {
+            let _ = storage.write_cron(cron);
+          } else {
+            status = STATUS_FAILED;
+            error_text = "Invalid cron action".to_string();
+          }
+        }
+        Err(_) => {
           status = STATUS_FAILED;
-          error_text = "Invalid cron action".to_string();
+          error_text = "Invalid cron".to_string();
         }
       }
-      Err(_) => {
-        status = STATUS_FAILED;
-        error_text = "Invalid cron".to_string();
-      }
     }
 
     let now = SystemTime::now();
@@ -434,8 +1440,45 @@ pub fn validate_cron(transaction_hash: String) {
       timestamp: timestamp.as_millis() as u64,
       error_text,
       data: "".to_string(),
+      meta_contract_version: 0,
     };
     storage.write_transaction_receipt(receipt);
 
     storage.update_transaction_status(transaction.hash.clone(), STATUS_DONE);
 }
+
+/**
+ * Keeps the version history and the content indexes in sync whenever a metadata record gets a new CID
+ */
+fn record_cid_update(
+    metadata: &Metadata,
+    cid: String,
+    previous_cid: String,
+    transaction_hash: String,
+    method: &str,
+    timestamp: u64,
+    content: &str,
+) {
+    let storage = get_storage();
+
+    let version = MetadataVersion::new(
+        metadata.hash.clone(),
+        cid,
+        previous_cid,
+        transaction_hash,
+        method.to_string(),
+        timestamp,
+    );
+
+    if let Err(e) = storage.write_metadata_version(version) {
+        log::info!("{}", e);
+    }
+
+    if let Err(e) = storage.index_metadata_content(metadata.hash.clone(), metadata.meta_contract_id.clone(), content) {
+        log::info!("{}", e);
+    }
+
+    if let Err(e) = storage.index_metadata_text(metadata.hash.clone(), content) {
+        log::info!("{}", e);
+    }
+}
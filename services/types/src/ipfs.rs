@@ -26,6 +26,28 @@ impl From<Result<String>> for IpfsDagPutResult {
     }
 }
 
+#[marine]
+#[derive(Debug)]
+pub struct IpfsUnpinResult {
+    pub success: bool,
+    pub error: String,
+}
+
+impl From<Result<String>> for IpfsUnpinResult {
+    fn from(result: Result<String>) -> Self {
+        match result {
+            Ok(_) => Self {
+                success: true,
+                error: "".to_string(),
+            },
+            Err(err) => Self {
+                success: false,
+                error: err.to_string(),
+            },
+        }
+    }
+}
+
 #[marine]
 #[derive(Debug)]
 pub struct IpfsDagGetResult {